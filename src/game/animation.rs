@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+
+use super::states::GameState;
+
+pub struct AnimationPlugin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipId {
+    Idle,
+    Walk,
+    Hurt,
+    Death,
+    Flicker,
+}
+
+// What a clip does after its last frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Looping {
+    Loop,
+    // Bounce back and forth between the first and last frame
+    PingPong,
+    // Hold the last frame and report finished
+    Once,
+}
+
+// A run of consecutive atlas frames played at `fps`
+#[derive(Debug, Clone, Copy)]
+pub struct Clip {
+    pub id: ClipId,
+    pub first: usize,
+    pub frames: usize,
+    pub fps: f32,
+    pub looping: Looping,
+}
+
+/// A sprite sheet's grid and the clips cut from it; each archetype declares one next to its spawn code.
+pub struct SheetDef {
+    pub tile: UVec2,
+    pub columns: usize,
+    pub rows: usize,
+    pub clips: &'static [Clip],
+}
+
+impl SheetDef {
+    pub fn layout(&self) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(self.tile.as_vec2(), self.columns, self.rows, None, None)
+    }
+
+    fn clip(&self, id: ClipId) -> &Clip {
+        self.clips.iter().find(|c| c.id == id).unwrap_or(&self.clips[0])
+    }
+}
+
+// Plays one clip of `sheet` at a time; gameplay systems pick the clip, `advance_animations` steps it
+#[derive(Component)]
+pub struct Animator {
+    sheet: &'static SheetDef,
+    clip: ClipId,
+    frame: usize,
+    elapsed: f32,
+    backwards: bool,
+    finished: bool,
+}
+
+impl Animator {
+    pub fn new(sheet: &'static SheetDef, clip: ClipId) -> Self {
+        Self { sheet, clip, frame: 0, elapsed: 0.0, backwards: false, finished: false }
+    }
+
+    /// Switch clips; asking for the clip already playing leaves it where it is.
+    pub fn play(&mut self, clip: ClipId) {
+        if self.clip != clip {
+            *self = Self::new(self.sheet, clip);
+        }
+    }
+
+    pub fn clip(&self) -> ClipId {
+        self.clip
+    }
+
+    // Only `Once` clips ever finish
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    // Atlas index of the current frame
+    pub fn index(&self) -> usize {
+        self.sheet.clip(self.clip).first + self.frame
+    }
+
+    fn advance(&mut self, dt: f32) {
+        let clip = *self.sheet.clip(self.clip);
+        if self.finished || (clip.frames <= 1 && clip.looping != Looping::Once) || clip.fps <= 0.0 { return; }
+        self.elapsed += dt;
+        let step = 1.0 / clip.fps;
+        while self.elapsed >= step && !self.finished {
+            self.elapsed -= step;
+            let last = clip.frames - 1;
+            match clip.looping {
+                Looping::Loop => self.frame = (self.frame + 1) % clip.frames,
+                Looping::Once => {
+                    if self.frame < last { self.frame += 1; } else { self.finished = true; }
+                }
+                Looping::PingPong => {
+                    if self.backwards && self.frame == 0 { self.backwards = false; }
+                    else if !self.backwards && self.frame == last { self.backwards = true; }
+                    self.frame = if self.backwards { self.frame - 1 } else { self.frame + 1 };
+                }
+            }
+        }
+    }
+}
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        // Keeps running on the GameOver screen so the player's death clip plays out
+        app.add_systems(PostUpdate, advance_animations
+            .run_if(in_state(GameState::Playing).or_else(in_state(GameState::GameOver))));
+    }
+}
+
+fn advance_animations(time: Res<Time>, mut sprites: Query<(&mut Animator, &mut TextureAtlas)>) {
+    let dt = time.delta_seconds();
+    for (mut animator, mut atlas) in sprites.iter_mut() {
+        animator.advance(dt);
+        let index = animator.index();
+        if atlas.index != index { atlas.index = index; }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHEET: SheetDef = SheetDef {
+        tile: UVec2::new(16, 16),
+        columns: 4,
+        rows: 2,
+        clips: &[
+            Clip { id: ClipId::Walk, first: 0, frames: 4, fps: 10.0, looping: Looping::Loop },
+            Clip { id: ClipId::Death, first: 4, frames: 2, fps: 10.0, looping: Looping::Once },
+            Clip { id: ClipId::Flicker, first: 4, frames: 3, fps: 10.0, looping: Looping::PingPong },
+        ],
+    };
+
+    #[test]
+    fn looping_modes() {
+        let mut walk = Animator::new(&SHEET, ClipId::Walk);
+        walk.advance(0.45);
+        assert_eq!(walk.index(), 0, "four frames at 10fps wrap after 0.4s");
+
+        let mut death = Animator::new(&SHEET, ClipId::Death);
+        death.advance(0.15);
+        assert_eq!((death.index(), death.finished()), (5, false));
+        death.advance(1.0);
+        assert_eq!((death.index(), death.finished()), (5, true));
+
+        let mut flicker = Animator::new(&SHEET, ClipId::Flicker);
+        let frames: Vec<usize> = (0..5).map(|_| { flicker.advance(0.1); flicker.index() }).collect();
+        assert_eq!(frames, vec![5, 6, 5, 4, 5]);
+    }
+
+    #[test]
+    fn replaying_the_current_clip_keeps_progress() {
+        let mut anim = Animator::new(&SHEET, ClipId::Walk);
+        anim.advance(0.25);
+        anim.play(ClipId::Walk);
+        assert_eq!(anim.index(), 2);
+        anim.play(ClipId::Death);
+        assert_eq!(anim.index(), 4);
+    }
+}
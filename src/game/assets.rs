@@ -1,5 +1,8 @@
+use bevy::asset::{LoadState, UntypedAssetLoadFailedEvent};
 use bevy::prelude::*;
 
+use super::enemy::ENEMY_SHEET;
+use super::player::{FLAME_SHEET, PLAYER_SHEET};
 use super::states::GameState;
 
 pub struct AssetsPlugin;
@@ -7,28 +10,175 @@ pub struct AssetsPlugin;
 #[derive(Resource, Default, Clone)]
 pub struct EnemyAssets {
     pub bucket: Handle<Image>,
+    pub bucket_layout: Handle<TextureAtlasLayout>,
 }
 
 #[derive(Resource, Default, Clone)]
 pub struct PlayerAssets {
     pub mainchar: Handle<Image>,
+    pub mainchar_layout: Handle<TextureAtlasLayout>,
     pub flame: Handle<Image>,
+    pub flame_layout: Handle<TextureAtlasLayout>,
+}
+
+const HEADER_FONT: &str = "fonts/DejaVuSans-Bold.ttf";
+const BODY_FONT: &str = "fonts/DejaVuSans.ttf";
+const NUMBERS_FONT: &str = "fonts/DejaVuSansMono.ttf";
+
+// UI fonts by role; a file that fails to load is swapped for Bevy's built-in font
+#[derive(Resource, Default, Clone)]
+pub struct UiFonts {
+    pub header: Handle<Font>,
+    pub body: Handle<Font>,
+    pub numbers: Handle<Font>,
+}
+
+impl UiFonts {
+    // The body font fell back to Bevy's built-in one, which has no glyphs beyond ASCII
+    pub fn ascii_only(&self) -> bool {
+        self.body == Handle::default()
+    }
+
+    // Panel titles and overlay headings
+    pub fn header(&self, font_size: f32, color: Color) -> TextStyle {
+        TextStyle { font: self.header.clone(), font_size, color }
+    }
+
+    // Buttons, descriptions and everything else
+    pub fn body(&self, font_size: f32, color: Color) -> TextStyle {
+        TextStyle { font: self.body.clone(), font_size, color }
+    }
+
+    // HUD counters and damage numbers
+    pub fn numbers(&self, font_size: f32, color: Color) -> TextStyle {
+        TextStyle { font: self.numbers.clone(), font_size, color }
+    }
+}
+
+// What the Loading screen shows; fonts count as done once they load or fall back
+#[derive(Resource, Default)]
+pub struct LoadingProgress {
+    pub done: usize,
+    pub total: usize,
+    // "path: reason" for each required asset that failed; loading stops until they're fixed
+    pub errors: Vec<String>,
+}
+
+impl LoadingProgress {
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 { 1.0 } else { self.done as f32 / self.total as f32 }
+    }
 }
 
 impl Plugin for AssetsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EnemyAssets>()
             .init_resource::<PlayerAssets>()
-            .add_systems(OnEnter(GameState::Playing), (load_enemy_assets, load_player_assets));
+            .init_resource::<UiFonts>()
+            .init_resource::<LoadingProgress>()
+            // Everything starts loading before any Startup UI is spawned
+            .add_systems(PreStartup, (load_enemy_assets, load_player_assets, load_ui_fonts))
+            .add_systems(Update, (
+                fallback_missing_fonts,
+                track_loading.after(fallback_missing_fonts).run_if(in_state(GameState::Loading)),
+            ));
+    }
+}
+
+// Leaves Loading once every sprite is in and every font has loaded or fallen back
+fn track_loading(
+    asset_server: Res<AssetServer>,
+    enemy: Res<EnemyAssets>,
+    player: Res<PlayerAssets>,
+    fonts: Res<UiFonts>,
+    mut failures: EventReader<UntypedAssetLoadFailedEvent>,
+    mut progress: ResMut<LoadingProgress>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    let required = [enemy.bucket.id(), player.mainchar.id(), player.flame.id()];
+    for failure in failures.read() {
+        if required.iter().any(|id| failure.id == id.untyped()) {
+            progress.errors.push(format!("{}: {}", failure.path, failure.error));
+        }
+    }
+    let sprites_done = required.iter().filter(|id| asset_server.load_state(**id) == LoadState::Loaded).count();
+    let fonts_done = [&fonts.header, &fonts.body, &fonts.numbers].into_iter()
+        .filter(|h| *h == &Handle::default() || matches!(asset_server.load_state(*h), LoadState::Loaded | LoadState::Failed))
+        .count();
+    progress.total = required.len() + 3;
+    progress.done = sprites_done + fonts_done;
+    if progress.done == progress.total {
+        next.set(GameState::MainMenu);
     }
 }
 
-fn load_enemy_assets(mut assets: ResMut<EnemyAssets>, asset_server: Res<AssetServer>) {
+fn load_ui_fonts(mut fonts: ResMut<UiFonts>, asset_server: Res<AssetServer>) {
+    fonts.header = asset_server.load(HEADER_FONT);
+    fonts.body = asset_server.load(BODY_FONT);
+    fonts.numbers = asset_server.load(NUMBERS_FONT);
+}
+
+// Repoint the resource and any text already spawned with a font that failed to load
+fn fallback_missing_fonts(
+    asset_server: Res<AssetServer>,
+    mut fonts: ResMut<UiFonts>,
+    mut texts: Query<&mut Text>,
+) {
+    let failed: Vec<Handle<Font>> = [&fonts.header, &fonts.body, &fonts.numbers].into_iter()
+        .filter(|h| *h != &Handle::default() && asset_server.load_state(*h) == LoadState::Failed)
+        .cloned()
+        .collect();
+    if failed.is_empty() { return; }
+    for handle in &failed {
+        if let Some(path) = handle.path() { warn!("font {path} unavailable, using the built-in font"); }
+    }
+    let fonts = fonts.as_mut();
+    for slot in [&mut fonts.header, &mut fonts.body, &mut fonts.numbers] {
+        if failed.contains(slot) { *slot = Handle::default(); }
+    }
+    for mut text in texts.iter_mut() {
+        for section in text.sections.iter_mut().filter(|s| failed.contains(&s.style.font)) {
+            section.style.font = Handle::default();
+        }
+    }
+}
+
+// Atlas layouts come from the sheet definitions next to each archetype
+fn load_enemy_assets(mut assets: ResMut<EnemyAssets>, asset_server: Res<AssetServer>, mut layouts: ResMut<Assets<TextureAtlasLayout>>) {
     // Correct path inside assets directory
     assets.bucket = asset_server.load("sprites/bucket.png");
+    assets.bucket_layout = layouts.add(ENEMY_SHEET.layout());
 }
 
-fn load_player_assets(mut assets: ResMut<PlayerAssets>, asset_server: Res<AssetServer>) {
+fn load_player_assets(mut assets: ResMut<PlayerAssets>, asset_server: Res<AssetServer>, mut layouts: ResMut<Assets<TextureAtlasLayout>>) {
     assets.mainchar = asset_server.load("sprites/mainchar.png");
+    assets.mainchar_layout = layouts.add(PLAYER_SHEET.layout());
     assets.flame = asset_server.load("sprites/flame.png");
+    assets.flame_layout = layouts.add(FLAME_SHEET.layout());
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::Font as _;
+
+    use super::*;
+    use crate::game::i18n::{parse_lang, Localization, LOCALES};
+
+    #[test]
+    fn shipped_fonts_cover_every_locale() {
+        for path in [HEADER_FONT, BODY_FONT, NUMBERS_FONT] {
+            let file = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets").join(path);
+            let bytes = std::fs::read(&file).unwrap_or_else(|e| panic!("{path} is not shipped: {e}"));
+            let font = ab_glyph::FontRef::try_from_slice(&bytes).unwrap_or_else(|e| panic!("{path}: {e}"));
+            for (id, source) in LOCALES {
+                let missing: String = parse_lang(source).values().flat_map(|v| v.chars())
+                    .filter(|c| !c.is_control() && font.glyph_id(*c).0 == 0)
+                    .collect();
+                assert!(missing.is_empty(), "{path} has no glyphs for {missing:?} used by '{id}'");
+            }
+        }
+        // The body font loads, so the picker offers every locale
+        let fonts_loaded = UiFonts { body: Handle::weak_from_u128(1), ..default() };
+        assert!(Localization::default().languages(fonts_loaded.ascii_only()).contains(&"fr"));
+    }
 }
@@ -0,0 +1,336 @@
+use bevy::audio::{AddAudioSource, AudioPlugin, AudioSink, AudioSinkPlayback, Decodable, PlaybackMode, Source, Volume};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::combat::EnemyHit;
+use super::states::GameState;
+
+pub struct GameAudioPlugin;
+
+// Hard cap on simultaneous sound effects across all kinds
+pub const MAX_SFX_VOICES: usize = 24;
+// Music level while a menu covers gameplay; Paused also pauses the track outright
+const MUSIC_DUCKED: f32 = 0.35;
+const MUSIC_DUCK_SPEED: f32 = 4.0;
+const SAMPLE_RATE: u32 = 44_100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sfx {
+    Hit,
+    Death,
+    XpPickup,
+    LevelUp,
+    PlayerHurt,
+    GameOver,
+}
+
+impl Sfx {
+    pub const ALL: [Sfx; 6] = [Sfx::Hit, Sfx::Death, Sfx::XpPickup, Sfx::LevelUp, Sfx::PlayerHurt, Sfx::GameOver];
+
+    // Voices of this kind allowed at once
+    fn max_voices(self) -> usize {
+        match self {
+            Sfx::Hit => 6,
+            Sfx::Death => 6,
+            Sfx::XpPickup => 4,
+            Sfx::LevelUp | Sfx::PlayerHurt | Sfx::GameOver => 1,
+        }
+    }
+
+    // Minimum seconds between two starts of this kind, so bursts read as one event
+    fn min_gap(self) -> f32 {
+        match self {
+            Sfx::Hit => 0.05,
+            Sfx::Death => 0.04,
+            Sfx::XpPickup => 0.06,
+            Sfx::PlayerHurt => 0.25,
+            Sfx::LevelUp | Sfx::GameOver => 0.0,
+        }
+    }
+
+    fn synth(self) -> SynthSound {
+        use Wave::*;
+        let n = |freq: f32, secs: f32| Note { freq, secs };
+        match self {
+            Sfx::Hit => SynthSound::new(Noise, 0.25, vec![n(900.0, 0.05)]),
+            Sfx::Death => SynthSound::new(Square, 0.18, vec![n(220.0, 0.05), n(140.0, 0.09)]),
+            Sfx::XpPickup => SynthSound::new(Sine, 0.3, vec![n(1320.0, 0.05), n(1760.0, 0.07)]),
+            Sfx::LevelUp => SynthSound::new(Square, 0.22, vec![n(523.3, 0.09), n(659.3, 0.09), n(784.0, 0.09), n(1046.5, 0.25)]),
+            Sfx::PlayerHurt => SynthSound::new(Square, 0.3, vec![n(180.0, 0.08), n(120.0, 0.12)]),
+            Sfx::GameOver => SynthSound::new(Triangle, 0.45, vec![n(392.0, 0.3), n(311.1, 0.3), n(261.6, 0.3), n(196.0, 0.8)]),
+        }
+    }
+}
+
+// Ask for a sound effect; the voice limiter decides whether it actually starts
+#[derive(Event, Clone, Copy)]
+pub struct PlaySfx(pub Sfx);
+
+// Channel volumes, multiplied together (master * channel) when a voice starts / each frame for music
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct AudioVolumes {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioVolumes {
+    fn default() -> Self { Self { master: 0.8, music: 0.5, sfx: 0.7 } }
+}
+
+#[derive(Clone, Copy)]
+pub enum Wave {
+    Sine,
+    Square,
+    Triangle,
+    Noise,
+}
+
+#[derive(Clone, Copy)]
+pub struct Note {
+    // 0 Hz is a rest
+    pub freq: f32,
+    pub secs: f32,
+}
+
+// Tiny procedural synth so the game needs no audio files: a note sequence on one waveform,
+// each note with a short attack and a decaying tail
+#[derive(Asset, TypePath, Clone)]
+pub struct SynthSound {
+    wave: Wave,
+    gain: f32,
+    notes: Arc<[Note]>,
+}
+
+impl SynthSound {
+    pub fn new(wave: Wave, gain: f32, notes: Vec<Note>) -> Self {
+        Self { wave, gain, notes: notes.into() }
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.notes.iter().map(|n| n.secs).sum()
+    }
+}
+
+pub struct SynthDecoder {
+    sound: SynthSound,
+    note: usize,
+    sample: u32,
+    phase: f32,
+    noise: u32,
+}
+
+impl Iterator for SynthDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let note = *self.sound.notes.get(self.note)?;
+        let len = (note.secs * SAMPLE_RATE as f32) as u32;
+        if self.sample >= len {
+            self.note += 1;
+            self.sample = 0;
+            return self.next();
+        }
+        let t = self.sample as f32 / len as f32;
+        self.sample += 1;
+        if note.freq <= 0.0 { return Some(0.0); }
+        let envelope = (t / 0.05).min(1.0) * (1.0 - t).powi(2);
+        self.phase = (self.phase + note.freq / SAMPLE_RATE as f32).fract();
+        let value = match self.sound.wave {
+            Wave::Sine => (self.phase * std::f32::consts::TAU).sin(),
+            Wave::Square => if self.phase < 0.5 { 1.0 } else { -1.0 },
+            Wave::Triangle => 1.0 - 4.0 * (self.phase - 0.5).abs(),
+            Wave::Noise => {
+                // xorshift; plenty for percussive hiss
+                self.noise ^= self.noise << 13;
+                self.noise ^= self.noise >> 17;
+                self.noise ^= self.noise << 5;
+                self.noise as f32 / u32::MAX as f32 * 2.0 - 1.0
+            }
+        };
+        Some(value * envelope * self.sound.gain)
+    }
+}
+
+impl Source for SynthDecoder {
+    fn current_frame_len(&self) -> Option<usize> { None }
+    fn channels(&self) -> u16 { 1 }
+    fn sample_rate(&self) -> u32 { SAMPLE_RATE }
+    fn total_duration(&self) -> Option<Duration> { Some(Duration::from_secs_f32(self.sound.duration())) }
+}
+
+impl Decodable for SynthSound {
+    type DecoderItem = f32;
+    type Decoder = SynthDecoder;
+
+    fn decoder(&self) -> SynthDecoder {
+        SynthDecoder { sound: self.clone(), note: 0, sample: 0, phase: 0.0, noise: 0x9E37_79B9 }
+    }
+}
+
+// Looping background track: a slow minor arpeggio
+fn music_synth() -> SynthSound {
+    let progression = [[220.0, 261.6, 329.6], [174.6, 220.0, 261.6], [196.0, 246.9, 293.7], [164.8, 207.7, 246.9]];
+    let mut notes = Vec::new();
+    for chord in progression {
+        for _ in 0..2 {
+            for freq in chord {
+                notes.push(Note { freq, secs: 0.3 });
+            }
+            notes.push(Note { freq: chord[0] * 2.0, secs: 0.3 });
+        }
+    }
+    SynthSound::new(Wave::Triangle, 0.35, notes)
+}
+
+// Built once at startup; absent when the app runs without `AudioPlugin`
+#[derive(Resource)]
+struct SfxLibrary {
+    sounds: HashMap<Sfx, (Handle<SynthSound>, f32)>,
+    last_started: HashMap<Sfx, f32>,
+}
+
+// A playing effect. Bevy despawns it when playback ends; `expires` also cleans up when there is
+// no output device and playback never starts.
+#[derive(Component)]
+struct SfxVoice {
+    kind: Sfx,
+    expires: f32,
+}
+
+#[derive(Component)]
+struct MusicTrack;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlaySfx>()
+            .init_resource::<AudioVolumes>();
+        if !app.is_plugin_added::<AudioPlugin>() {
+            info!("AudioPlugin not present; running silent");
+            return;
+        }
+        app.add_audio_source::<SynthSound>()
+            .add_systems(Startup, setup_audio)
+            .add_systems(OnEnter(GameState::LevelUp), |mut sfx: EventWriter<PlaySfx>| { sfx.send(PlaySfx(Sfx::LevelUp)); })
+            .add_systems(OnEnter(GameState::GameOver), |mut sfx: EventWriter<PlaySfx>| { sfx.send(PlaySfx(Sfx::GameOver)); })
+            .add_systems(PostUpdate, (hit_sounds, expire_sfx_voices, play_sfx, duck_music).chain());
+    }
+}
+
+fn setup_audio(mut commands: Commands, mut sounds: ResMut<Assets<SynthSound>>, volumes: Res<AudioVolumes>) {
+    let library = Sfx::ALL.iter()
+        .map(|kind| {
+            let synth = kind.synth();
+            let secs = synth.duration();
+            (*kind, (sounds.add(synth), secs))
+        })
+        .collect();
+    commands.insert_resource(SfxLibrary { sounds: library, last_started: HashMap::default() });
+    commands.spawn((MusicTrack, AudioSourceBundle {
+        source: sounds.add(music_synth()),
+        settings: PlaybackSettings { mode: PlaybackMode::Loop, volume: Volume::new(volumes.master * volumes.music), ..default() },
+    }));
+}
+
+// Any number of hits in a frame asks for one hit sound; the limiter handles the rest
+fn hit_sounds(mut hits: EventReader<EnemyHit>, mut sfx: EventWriter<PlaySfx>) {
+    if hits.is_empty() { return; }
+    hits.clear();
+    sfx.send(PlaySfx(Sfx::Hit));
+}
+
+/// Which of this frame's requests may start: duplicates collapse to one, then per-kind caps,
+/// the per-kind cooldown and the global voice cap apply.
+pub fn admit_voices(
+    requests: impl IntoIterator<Item = Sfx>,
+    live: &HashMap<Sfx, usize>,
+    last_started: &HashMap<Sfx, f32>,
+    now: f32,
+) -> Vec<Sfx> {
+    let mut total: usize = live.values().sum();
+    let mut admitted = Vec::new();
+    for kind in requests {
+        if total >= MAX_SFX_VOICES { break; }
+        if admitted.contains(&kind) { continue; }
+        if live.get(&kind).copied().unwrap_or(0) >= kind.max_voices() { continue; }
+        if last_started.get(&kind).is_some_and(|t| now - t < kind.min_gap()) { continue; }
+        admitted.push(kind);
+        total += 1;
+    }
+    admitted
+}
+
+fn play_sfx(
+    mut commands: Commands,
+    mut requests: EventReader<PlaySfx>,
+    mut library: ResMut<SfxLibrary>,
+    voices: Query<&SfxVoice>,
+    volumes: Res<AudioVolumes>,
+    time: Res<Time>,
+) {
+    if requests.is_empty() { return; }
+    let mut live: HashMap<Sfx, usize> = HashMap::default();
+    for voice in voices.iter() {
+        *live.entry(voice.kind).or_default() += 1;
+    }
+    let now = time.elapsed_seconds();
+    for kind in admit_voices(requests.read().map(|r| r.0), &live, &library.last_started, now) {
+        let Some((handle, secs)) = library.sounds.get(&kind).cloned() else { continue; };
+        library.last_started.insert(kind, now);
+        commands.spawn((SfxVoice { kind, expires: now + secs + 0.5 }, AudioSourceBundle {
+            source: handle,
+            settings: PlaybackSettings { volume: Volume::new(volumes.master * volumes.sfx), ..PlaybackSettings::DESPAWN },
+        }));
+    }
+}
+
+fn expire_sfx_voices(mut commands: Commands, voices: Query<(Entity, &SfxVoice)>, time: Res<Time>) {
+    let now = time.elapsed_seconds();
+    for (entity, voice) in voices.iter() {
+        if now > voice.expires { commands.entity(entity).despawn(); }
+    }
+}
+
+// Music follows the game state: full while playing, ducked under menus, paused while Paused
+fn duck_music(
+    state: Res<State<GameState>>,
+    volumes: Res<AudioVolumes>,
+    time: Res<Time>,
+    mut duck: Local<f32>,
+    music: Query<&AudioSink, With<MusicTrack>>,
+) {
+    let (target, paused) = match state.get() {
+        GameState::Loading | GameState::Playing => (1.0, false),
+        GameState::MainMenu | GameState::LevelUp | GameState::ChestOpen | GameState::GameOver => (MUSIC_DUCKED, false),
+        GameState::Paused => (MUSIC_DUCKED, true),
+    };
+    *duck += (target - *duck) * (MUSIC_DUCK_SPEED * time.delta_seconds()).min(1.0);
+    for sink in music.iter() {
+        sink.set_volume(volumes.master * volumes.music * *duck);
+        if paused && !sink.is_paused() { sink.pause(); }
+        if !paused && sink.is_paused() { sink.play(); }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_of_hits_starts_one_voice() {
+        let admitted = admit_voices(std::iter::repeat_n(Sfx::Hit, 200), &HashMap::default(), &HashMap::default(), 1.0);
+        assert_eq!(admitted, vec![Sfx::Hit]);
+    }
+
+    #[test]
+    fn caps_and_cooldown_are_respected() {
+        let full = HashMap::from_iter([(Sfx::Hit, Sfx::Hit.max_voices())]);
+        assert!(admit_voices([Sfx::Hit], &full, &HashMap::default(), 1.0).is_empty());
+        let recent = HashMap::from_iter([(Sfx::Hit, 0.99)]);
+        assert!(admit_voices([Sfx::Hit], &HashMap::default(), &recent, 1.0).is_empty());
+        let crowded = HashMap::from_iter([(Sfx::Death, MAX_SFX_VOICES)]);
+        assert!(admit_voices([Sfx::LevelUp], &crowded, &HashMap::default(), 1.0).is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use super::camera::MainCamera;
+
+pub struct BackgroundPlugin;
+
+// Same seed => same ground everywhere
+#[derive(Resource)]
+pub struct WorldSeed(pub u64);
+
+impl Default for WorldSeed {
+    fn default() -> Self { Self(0x510F_ACE5) }
+}
+
+#[derive(Component)]
+pub struct GroundChunk {
+    pub coord: IVec2,
+}
+
+#[derive(Component)]
+struct GroundTile {
+    index: usize,
+}
+
+// Chunk coord the streamer last centred on; avoids re-walking chunks every frame
+#[derive(Resource, Default)]
+struct StreamedCenter(Option<IVec2>);
+
+const TILE_SIZE: f32 = 64.0;
+const CHUNK_TILES: usize = 8;
+const CHUNK_SIZE: f32 = TILE_SIZE * CHUNK_TILES as f32;
+// Camera2d sees z in (-0.1, 999.9]; stay just below orbs (z 0) but inside the frustum
+const GROUND_Z: f32 = -0.05;
+
+// Scorched-earth palette; the tail entries are rarer decorations (embers, ash, bones)
+const GROUND_COLORS: &[Color] = &[
+    Color::rgb(0.16, 0.12, 0.10),
+    Color::rgb(0.18, 0.13, 0.10),
+    Color::rgb(0.15, 0.11, 0.09),
+    Color::rgb(0.17, 0.14, 0.11),
+];
+const DECOR_COLORS: &[Color] = &[
+    Color::rgb(0.35, 0.12, 0.06),
+    Color::rgb(0.24, 0.22, 0.20),
+    Color::rgb(0.42, 0.39, 0.33),
+];
+const DECOR_CHANCE_PER_256: u64 = 10;
+
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldSeed>()
+            .init_resource::<StreamedCenter>()
+            .add_systems(Update, stream_ground_chunks);
+    }
+}
+
+// splitmix64 over (seed, x, y, i): stable across platforms and rand versions
+pub fn tile_hash(seed: u64, x: i32, y: i32, i: u64) -> u64 {
+    let mut z = seed
+        ^ (x as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u32 as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+        ^ i.wrapping_mul(0x1656_67B1_9E37_79F9);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn tile_color(seed: u64, coord: IVec2, index: usize) -> Color {
+    let h = tile_hash(seed, coord.x, coord.y, index as u64);
+    if h % 256 < DECOR_CHANCE_PER_256 {
+        DECOR_COLORS[(h >> 8) as usize % DECOR_COLORS.len()]
+    } else {
+        GROUND_COLORS[(h >> 8) as usize % GROUND_COLORS.len()]
+    }
+}
+
+fn chunk_origin(coord: IVec2) -> Vec3 {
+    Vec3::new(coord.x as f32 * CHUNK_SIZE, coord.y as f32 * CHUNK_SIZE, GROUND_Z)
+}
+
+fn stream_ground_chunks(
+    mut commands: Commands,
+    seed: Res<WorldSeed>,
+    mut center: ResMut<StreamedCenter>,
+    camera_q: Query<(&Camera, &Transform), With<MainCamera>>,
+    mut chunks: Query<(&mut GroundChunk, &mut Transform, &Children), Without<MainCamera>>,
+    mut tiles: Query<(&GroundTile, &mut Sprite)>,
+) {
+    let Ok((camera, cam_tf)) = camera_q.get_single() else { return; };
+    let Some(view) = camera.logical_viewport_size() else { return; };
+    let cam_chunk = (cam_tf.translation.truncate() / CHUNK_SIZE).floor().as_ivec2();
+    if center.0 == Some(cam_chunk) && !seed.is_changed() { return; }
+    center.0 = Some(cam_chunk);
+
+    // Enough chunks to cover the view plus a one-chunk margin on every side
+    let reach = (view / CHUNK_SIZE / 2.0).ceil().as_ivec2() + IVec2::ONE;
+    let mut needed: HashSet<IVec2> = HashSet::default();
+    for y in -reach.y..=reach.y {
+        for x in -reach.x..=reach.x {
+            needed.insert(cam_chunk + IVec2::new(x, y));
+        }
+    }
+
+    // Recycle chunks that fell out of range into the coords that just came into range
+    let stale: Vec<IVec2> = chunks.iter().filter(|(c, _, _)| !needed.remove(&c.coord)).map(|(c, _, _)| c.coord).collect();
+    let reseed = seed.is_changed();
+    let mut missing = needed.into_iter();
+    for (mut chunk, mut tf, children) in chunks.iter_mut() {
+        let mut moved = false;
+        if stale.contains(&chunk.coord) {
+            if let Some(coord) = missing.next() {
+                chunk.coord = coord;
+                tf.translation = chunk_origin(coord);
+                moved = true;
+            }
+        }
+        if !moved && !reseed { continue; }
+        for &child in children.iter() {
+            if let Ok((tile, mut sprite)) = tiles.get_mut(child) {
+                sprite.color = tile_color(seed.0, chunk.coord, tile.index);
+            }
+        }
+    }
+    for coord in missing {
+        spawn_chunk(&mut commands, seed.0, coord);
+    }
+}
+
+fn spawn_chunk(commands: &mut Commands, seed: u64, coord: IVec2) {
+    commands.spawn((GroundChunk { coord }, SpatialBundle::from_transform(Transform::from_translation(chunk_origin(coord)))))
+        .with_children(|parent| {
+            for index in 0..CHUNK_TILES * CHUNK_TILES {
+                let (tx, ty) = ((index % CHUNK_TILES) as f32, (index / CHUNK_TILES) as f32);
+                parent.spawn((GroundTile { index }, SpriteBundle {
+                    sprite: Sprite { color: tile_color(seed, coord, index), custom_size: Some(Vec2::splat(TILE_SIZE)), ..default() },
+                    // Tile centres, so the chunk spans [origin, origin + CHUNK_SIZE)
+                    transform: Transform::from_xyz((tx + 0.5) * TILE_SIZE, (ty + 0.5) * TILE_SIZE, 0.0),
+                    ..default()
+                }));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::camera::{camera_system, ManualTextureViews};
+    use bevy::window::{PrimaryWindow, WindowCreated, WindowResized, WindowResolution, WindowScaleFactorChanged};
+
+    fn chunk_colors(seed: u64, coord: IVec2) -> Vec<Color> {
+        (0..CHUNK_TILES * CHUNK_TILES).map(|i| tile_color(seed, coord, i)).collect()
+    }
+
+    #[test]
+    fn tile_hash_is_pinned() {
+        let coord = IVec2::new(-3, 7);
+        assert_eq!(tile_hash(42, coord.x, coord.y, 0), 0xE19A_3584_6094_44ED);
+        assert_eq!(tile_color(42, coord, 0), GROUND_COLORS[0]);
+        // Low byte 3 is under DECOR_CHANCE_PER_256, so this tile is a decoration
+        assert_eq!(tile_color(42, coord, 9), DECOR_COLORS[2]);
+        assert_ne!(tile_hash(42, coord.x, coord.y, 0), tile_hash(43, coord.x, coord.y, 0));
+        assert_ne!(chunk_colors(42, coord), chunk_colors(43, coord));
+    }
+
+    // Headless cameras have no viewport size until camera_system sees a window to render to
+    fn app_with_camera() -> App {
+        let mut app = App::new();
+        app.insert_resource(WorldSeed(42))
+            .init_resource::<StreamedCenter>()
+            .init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<AssetEvent<Image>>()
+            .add_systems(Update, (camera_system::<OrthographicProjection>, stream_ground_chunks).chain());
+        app.world.spawn((Window { resolution: WindowResolution::new(800.0, 600.0), ..default() }, PrimaryWindow));
+        app.world.spawn((Camera2dBundle::default(), MainCamera));
+        app
+    }
+
+    fn chunks(app: &mut App) -> Vec<(Entity, IVec2)> {
+        let mut q = app.world.query::<(Entity, &GroundChunk)>();
+        q.iter(&app.world).map(|(e, c)| (e, c.coord)).collect()
+    }
+
+    #[test]
+    fn recycled_chunks_match_fresh_ones() {
+        let mut app = app_with_camera();
+        app.update();
+        let before = chunks(&mut app);
+        assert!(!before.is_empty());
+
+        let mut cam = app.world.query_filtered::<&mut Transform, With<MainCamera>>();
+        cam.single_mut(&mut app.world).translation.x += 3.0 * CHUNK_SIZE;
+        app.update();
+        let after = chunks(&mut app);
+        assert_eq!(after.len(), before.len(), "chunks that left the view are reused, not respawned");
+        let recycled: Vec<(Entity, IVec2)> = after.iter().copied()
+            .filter(|(e, coord)| before.iter().any(|(b, old)| b == e && old != coord))
+            .collect();
+        assert!(!recycled.is_empty());
+
+        let mut tiles = app.world.query::<(&GroundTile, &Sprite)>();
+        for (entity, coord) in recycled {
+            let children = app.world.get::<Children>(entity).unwrap().to_vec();
+            for child in children {
+                let (tile, sprite) = tiles.get(&app.world, child).unwrap();
+                assert_eq!(sprite.color, tile_color(42, coord, tile.index), "chunk {coord} tile {}", tile.index);
+            }
+        }
+    }
+}
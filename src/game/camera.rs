@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+use super::player::Player;
+
+pub struct CameraPlugin;
+
+#[derive(Component)]
+pub struct MainCamera;
+
+// Follow tuning plus the rig's own state (focus point and current trauma)
+#[derive(Resource)]
+pub struct CameraRig {
+    // Higher = snappier; 0 disables smoothing
+    pub smoothing: f32,
+    // Half-size of the box around the focus the player may roam without moving the camera
+    pub deadzone: Vec2,
+    // World rect the view must stay inside (bounded arenas); None = endless map
+    pub bounds: Option<Rect>,
+    pub max_shake_offset: f32,
+    pub max_shake_angle: f32,
+    // Player preference (settings); 0 turns shake off
+    pub shake_scale: f32,
+    // Trauma lost per second
+    pub trauma_decay: f32,
+    pub focus: Vec2,
+    pub trauma: f32,
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        Self {
+            smoothing: 8.0,
+            deadzone: Vec2::new(40.0, 30.0),
+            bounds: None,
+            max_shake_offset: 14.0,
+            max_shake_angle: 0.04,
+            shake_scale: 1.0,
+            trauma_decay: 1.5,
+            focus: Vec2::ZERO,
+            trauma: 0.0,
+        }
+    }
+}
+
+impl CameraRig {
+    /// Back to the origin with no shake; tuning and player preferences are kept.
+    pub fn recenter(&mut self) {
+        self.focus = Vec2::ZERO;
+        self.trauma = 0.0;
+    }
+}
+
+/// World-space rectangle currently visible through `camera`.
+pub fn visible_world_rect(camera: &Camera, cam_tf: &GlobalTransform) -> Option<Rect> {
+    let size = camera.logical_viewport_size()?;
+    let a = camera.viewport_to_world_2d(cam_tf, Vec2::ZERO)?;
+    let b = camera.viewport_to_world_2d(cam_tf, size)?;
+    Some(Rect::from_corners(a, b))
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CameraFollowSet;
+
+// Shake request; amounts add up and are clamped to 1.0
+#[derive(Event)]
+pub struct CameraTrauma(pub f32);
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraRig>()
+            .add_event::<CameraTrauma>()
+            // Up before the first state so the Loading screen has something to render through
+            .add_systems(Startup, spawn_camera)
+            .add_systems(PostUpdate, (
+                collect_trauma,
+                follow_player,
+            ).chain().in_set(CameraFollowSet).before(TransformSystem::TransformPropagate));
+    }
+}
+
+fn spawn_camera(mut commands: Commands, existing: Query<Entity, With<MainCamera>>, mut rig: ResMut<CameraRig>) {
+    if existing.iter().next().is_some() { return; }
+    rig.recenter();
+    commands.spawn((MainCamera, Camera2dBundle::default()));
+}
+
+fn collect_trauma(mut events: EventReader<CameraTrauma>, mut rig: ResMut<CameraRig>) {
+    for CameraTrauma(amount) in events.read() {
+        rig.trauma = (rig.trauma + amount).clamp(0.0, 1.0);
+    }
+}
+
+fn follow_player(
+    time: Res<Time>,
+    mut rig: ResMut<CameraRig>,
+    player: Query<&Transform, (With<Player>, Without<MainCamera>)>,
+    mut camera_q: Query<(&Camera, &mut Transform), With<MainCamera>>,
+) {
+    let Ok((camera, mut cam_tf)) = camera_q.get_single_mut() else { return; };
+    let dt = time.delta_seconds();
+    if let Ok(player_tf) = player.get_single() {
+        // Only chase the part of the offset that leaves the deadzone
+        let offset = player_tf.translation.truncate() - rig.focus;
+        let excess = offset - offset.clamp(-rig.deadzone, rig.deadzone);
+        let desired = rig.focus + excess;
+        let t = if rig.smoothing > 0.0 { 1.0 - (-rig.smoothing * dt).exp() } else { 1.0 };
+        rig.focus = rig.focus.lerp(desired, t);
+    }
+    if let (Some(bounds), Some(view)) = (rig.bounds, camera.logical_viewport_size()) {
+        let half = view * 0.5;
+        // A view larger than the arena just centers on it
+        let min = bounds.min + half;
+        let max = bounds.max - half;
+        rig.focus = Vec2::new(
+            if min.x <= max.x { rig.focus.x.clamp(min.x, max.x) } else { bounds.center().x },
+            if min.y <= max.y { rig.focus.y.clamp(min.y, max.y) } else { bounds.center().y },
+        );
+    }
+
+    // Trauma-based shake: squared for a nicer falloff, smooth pseudo-noise from layered sines
+    let shake = rig.trauma * rig.trauma * rig.shake_scale;
+    let t = time.elapsed_seconds();
+    let noise = |seed: f32| ((t * 37.0 + seed).sin() + (t * 23.0 + seed * 1.7).sin() * 0.5) / 1.5;
+    let shake_offset = Vec2::new(noise(1.0), noise(7.0)) * rig.max_shake_offset * shake;
+    let shake_angle = noise(13.0) * rig.max_shake_angle * shake;
+    rig.trauma = (rig.trauma - rig.trauma_decay * dt).max(0.0);
+
+    cam_tf.translation.x = rig.focus.x + shake_offset.x;
+    cam_tf.translation.y = rig.focus.y + shake_offset.y;
+    cam_tf.rotation = Quat::from_rotation_z(shake_angle);
+}
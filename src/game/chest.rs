@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use super::evolution::{EvolutionRecipe, EvolveWeapon, Loadout};
+use super::combat::CombatSet;
+use super::player::{Player, PlayerStats};
+use super::states::GameState;
+use super::upgrades::{UpgradeContext, UpgradeKind, UPGRADES};
+use super::xp::{enter_levelup_when_pending, PendingLevelUps};
+
+pub struct ChestPlugin;
+
+#[derive(Component)]
+pub struct Chest;
+
+// What the chest being opened granted; the UI reveals entries one by one
+#[derive(Resource)]
+pub struct ChestReward {
+    pub upgrades: Vec<UpgradeKind>,
+    pub evolution: Option<&'static EvolutionRecipe>,
+    pub revealed: usize,
+    pub reveal_timer: Timer,
+}
+
+impl Default for ChestReward {
+    fn default() -> Self {
+        Self { upgrades: Vec::new(), evolution: None, revealed: 0, reveal_timer: Timer::from_seconds(0.5, TimerMode::Repeating) }
+    }
+}
+
+impl ChestReward {
+    pub fn total(&self) -> usize {
+        if self.evolution.is_some() { 1 } else { self.upgrades.len() }
+    }
+    pub fn fully_revealed(&self) -> bool { self.revealed >= self.total() }
+}
+
+const CHEST_PICKUP_DISTANCE: f32 = 32.0;
+// Weighted upgrade counts: (count, weight)
+const CHEST_ROLLS: &[(usize, u32)] = &[(1, 70), (3, 25), (5, 5)];
+
+pub fn spawn_chest_at(commands: &mut Commands, pos: Vec2) {
+    commands.spawn((
+        Chest,
+        SpriteBundle {
+            sprite: Sprite { color: Color::rgb(0.95, 0.75, 0.2), custom_size: Some(Vec2::new(22.0, 16.0)), ..default() },
+            transform: Transform::from_xyz(pos.x, pos.y, 0.5),
+            ..default()
+        },
+    ));
+}
+
+impl Plugin for ChestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChestReward>()
+            .add_systems(Update, pickup_chests.after(CombatSet).after(enter_levelup_when_pending).run_if(in_state(GameState::Playing)))
+            .add_systems(OnEnter(GameState::ChestOpen), roll_chest_reward);
+    }
+}
+
+fn pickup_chests(
+    mut commands: Commands,
+    player: Query<&Transform, With<Player>>,
+    chests: Query<(Entity, &Transform), With<Chest>>,
+    pending: Res<PendingLevelUps>,
+    stats: Res<PlayerStats>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    // A queued level-up claims this frame's transition; the chest stays put until the player is back.
+    // Likewise a killing blow this frame has already sent the run to GameOver
+    if pending.0 > 0 || stats.hp <= 0.0 { return; }
+    let Ok(player_tf) = player.get_single() else { return; };
+    let ppos = player_tf.translation.truncate();
+    // Open one chest per frame; others wait until the reveal is dismissed
+    if let Some((entity, _)) = chests.iter().find(|(_, tf)| tf.translation.truncate().distance(ppos) < CHEST_PICKUP_DISTANCE) {
+        commands.entity(entity).despawn_recursive();
+        next.set(GameState::ChestOpen);
+    }
+}
+
+fn roll_chest_reward(
+    mut reward: ResMut<ChestReward>,
+    loadout: Res<Loadout>,
+    mut evolve: EventWriter<EvolveWeapon>,
+    mut upgrades: UpgradeContext,
+) {
+    *reward = ChestReward::default();
+    // An eligible evolution always wins over plain upgrades
+    if let Some(recipe) = loadout.eligible_evolution() {
+        evolve.send(EvolveWeapon(recipe.result));
+        reward.evolution = Some(recipe);
+        return;
+    }
+    let mut rng = rand::rng();
+    let total_weight: u32 = CHEST_ROLLS.iter().map(|(_, w)| w).sum();
+    let mut roll = rng.random_range(0..total_weight);
+    let count = CHEST_ROLLS.iter().find(|(_, w)| { if roll < *w { true } else { roll -= w; false } }).map_or(1, |(c, _)| *c);
+    for _ in 0..count {
+        let Some(def) = UPGRADES.choose(&mut rng) else { break; };
+        upgrades.apply(def.kind);
+        reward.upgrades.push(def.kind);
+    }
+}
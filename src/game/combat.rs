@@ -1,10 +1,17 @@
 use bevy::prelude::*;
 
-use super::enemy::{Enemy, EnemyHealth};
+use super::enemy::{Enemy, EnemyHealth, EnemyRank};
+use super::chest::spawn_chest_at;
+use super::pickups::maybe_spawn_pickup_at;
+use super::audio::{PlaySfx, Sfx};
+use super::particles::{ParticleBurst, EMBER_BURST};
+use super::camera::CameraTrauma;
+use super::spawn::release_enemy;
 use super::player::{Player, PlayerStats};
 use super::xp::spawn_xp_orb_at;
 use super::states::GameState;
 use super::player::OrbitingFlame;
+use super::status::{StatusEffects, StatusKind};
 
 pub struct CombatPlugin;
 
@@ -30,15 +37,42 @@ pub struct AuraTickTimer(Timer);
 #[derive(Component)]
 pub struct Damage(pub f32);
 
+// An enemy took damage; drives hit sounds and floating damage numbers
+#[derive(Event, Clone, Copy)]
+pub struct EnemyHit {
+    pub pos: Vec2,
+    pub amount: f32,
+}
+
+// Damage systems run chained in this set so a kill is applied before the next system looks at hp
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CombatSet;
+
+// Shared death path: drop XP (a chest for elites/bosses, maybe a map pickup), then return the enemy to the pool
+pub fn kill_enemy(commands: &mut Commands, entity: Entity, pos: Vec2, rank: EnemyRank) {
+    spawn_xp_orb_at(commands, pos, rank.xp_value());
+    if rank.drops_chest() {
+        spawn_chest_at(commands, pos);
+    } else {
+        maybe_spawn_pickup_at(commands, pos);
+    }
+    release_enemy(commands, entity);
+    commands.add(move |world: &mut World| {
+        world.send_event(PlaySfx(Sfx::Death));
+        world.send_event(ParticleBurst { pos, def: &EMBER_BURST, count: 14 });
+    });
+}
+
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<AuraTickTimer>()
+        app.add_event::<EnemyHit>()
+            .init_resource::<AuraTickTimer>()
             .init_resource::<AuraConfig>()
             .add_systems(Update, (
                 collision_combat,
                 aura_tick_damage,
                 flames_contact_damage,
-            ).run_if(in_state(GameState::Playing)));
+            ).chain().in_set(CombatSet).run_if(in_state(GameState::Playing)));
     }
 }
 
@@ -55,12 +89,14 @@ fn collision_combat(
     mut commands: Commands,
     mut stats: ResMut<PlayerStats>,
     players: Query<&Transform, With<Player>>,
-    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth), With<Enemy>>,
+    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth, &EnemyRank), With<Enemy>>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut trauma: EventWriter<CameraTrauma>,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
     let Ok(player_tf) = players.get_single() else { return; };
     let mut took_damage = false;
-    for (enemy_entity, tf, mut eh) in enemies.iter_mut() {
+    for (enemy_entity, tf, mut eh, rank) in enemies.iter_mut() {
         let dist = player_tf.translation.truncate().distance(tf.translation.truncate());
         if dist < COLLISION_DISTANCE {
             stats.apply_damage(COLLISION_PLAYER_DAMAGE);
@@ -68,35 +104,40 @@ fn collision_combat(
             eh.hp -= COLLISION_ENEMY_DAMAGE;
         }
         if eh.hp <= 0.0 {
-            spawn_xp_orb_at(&mut commands, tf.translation.truncate(), 5);
-            commands.entity(enemy_entity).despawn_recursive();
+            kill_enemy(&mut commands, enemy_entity, tf.translation.truncate(), *rank);
         }
     }
+    if took_damage {
+        trauma.send(CameraTrauma(0.03));
+        sfx.send(PlaySfx(Sfx::PlayerHurt));
+    }
     if took_damage && stats.hp <= 0.0 {
         next_state.set(GameState::GameOver);
     }
 }
 
-// Flames do contact damage on touch in addition to the periodic aura damage
+// Flames do contact damage on touch (and set enemies Burning) in addition to the periodic aura damage
 fn flames_contact_damage(
     mut commands: Commands,
-    flames: Query<&GlobalTransform, With<OrbitingFlame>>,
-    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth), With<Enemy>>,
+    flames: Query<(&GlobalTransform, &OrbitingFlame)>,
+    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth, &EnemyRank, &mut StatusEffects), With<Enemy>>,
+    mut hits: EventWriter<EnemyHit>,
 ) {
-    // Build a list of flame world positions once
-    let mut flame_positions: Vec<Vec2> = Vec::new();
-    for tf in flames.iter() {
+    // Build a list of flame world positions (with each flame's reach and damage) once
+    let mut flame_positions: Vec<(Vec2, f32, f32)> = Vec::new();
+    for (tf, of) in flames.iter() {
         let pos = tf.compute_transform().translation.truncate();
-        flame_positions.push(pos);
+        flame_positions.push((pos, of.contact_radius, of.contact_damage));
     }
-    for (entity, tf, mut eh) in enemies.iter_mut() {
+    for (entity, tf, mut eh, rank, mut status) in enemies.iter_mut() {
         let epos = tf.translation.truncate();
-        for fpos in flame_positions.iter() {
-            if epos.distance(*fpos) < 16.0 {
-                eh.hp -= 5.0;
+        for (fpos, reach, damage) in flame_positions.iter() {
+            if epos.distance(*fpos) < *reach {
+                eh.hp -= *damage;
+                status.apply(StatusKind::Burning, 1);
+                hits.send(EnemyHit { pos: epos, amount: *damage });
                 if eh.hp <= 0.0 {
-                    spawn_xp_orb_at(&mut commands, tf.translation.truncate(), 5);
-                    commands.entity(entity).despawn_recursive();
+                    kill_enemy(&mut commands, entity, tf.translation.truncate(), *rank);
                 }
                 break;
             }
@@ -109,20 +150,21 @@ fn aura_tick_damage(
     time: Res<Time>,
     mut timer: ResMut<AuraTickTimer>,
     player: Query<&Transform, With<Player>>, // use player world transform (child aura was local)
-    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth), With<Enemy>>,
+    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth, &EnemyRank), With<Enemy>>,
     cfg: Res<AuraConfig>,
+    mut hits: EventWriter<EnemyHit>,
 ) {
     if !timer.0.tick(time.delta()).just_finished() { return; }
     let Ok(player_tf) = player.get_single() else { return; };
     let center = player_tf.translation.truncate();
     let radius_sq = cfg.radius * cfg.radius;
-    for (entity, tf, mut eh) in enemies.iter_mut() {
+    for (entity, tf, mut eh, rank) in enemies.iter_mut() {
         let dist_sq = center.distance_squared(tf.translation.truncate());
         if dist_sq < radius_sq {
             eh.hp -= cfg.damage_tick;
+            hits.send(EnemyHit { pos: tf.translation.truncate(), amount: cfg.damage_tick });
             if eh.hp <= 0.0 {
-                spawn_xp_orb_at(&mut commands, tf.translation.truncate(), 5);
-                commands.entity(entity).despawn_recursive();
+                kill_enemy(&mut commands, entity, tf.translation.truncate(), *rank);
             }
         }
     }
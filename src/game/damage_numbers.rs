@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use super::assets::UiFonts;
+use super::combat::{CombatSet, EnemyHit};
+use super::settings::Preferences;
+use super::states::GameState;
+
+pub struct DamageNumbersPlugin;
+
+// Oldest numbers are not replaced; new hits past the cap are simply not shown
+const MAX_DAMAGE_NUMBERS: usize = 80;
+const LIFETIME: f32 = 0.6;
+const RISE_SPEED: f32 = 40.0;
+
+#[derive(Component, Default)]
+pub struct DamageNumber {
+    age: f32,
+}
+
+impl Plugin for DamageNumbersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            spawn_damage_numbers.after(CombatSet),
+            animate_damage_numbers,
+        ).run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn spawn_damage_numbers(
+    mut commands: Commands,
+    mut hits: EventReader<EnemyHit>,
+    prefs: Res<Preferences>,
+    fonts: Res<UiFonts>,
+    live: Query<(), With<DamageNumber>>,
+) {
+    if !prefs.damage_numbers {
+        hits.clear();
+        return;
+    }
+    let room = MAX_DAMAGE_NUMBERS.saturating_sub(live.iter().count());
+    for hit in hits.read().take(room) {
+        commands.spawn((DamageNumber::default(), Text2dBundle {
+            text: Text::from_section(
+                format!("{:.0}", hit.amount),
+                fonts.numbers(16.0, Color::rgb(1.0, 0.95, 0.6)),
+            ),
+            transform: Transform::from_translation((hit.pos + Vec2::new(0.0, 16.0)).extend(5.0)),
+            ..default()
+        }));
+    }
+    hits.clear();
+}
+
+// Float up and fade out
+fn animate_damage_numbers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut numbers: Query<(Entity, &mut DamageNumber, &mut Transform, &mut Text)>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, mut number, mut tf, mut text) in numbers.iter_mut() {
+        number.age += dt;
+        if number.age >= LIFETIME {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        tf.translation.y += RISE_SPEED * dt;
+        text.sections[0].style.color.set_a(1.0 - number.age / LIFETIME);
+    }
+}
@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 
+use super::animation::{Clip, ClipId, Looping, SheetDef};
 use super::player::Player;
+use super::pickups::EnemyFreeze;
+use super::flowfield::{FlowField, FlowFieldSet};
 use super::states::GameState;
+use super::status::StatusEffects;
 
 pub struct EnemyPlugin;
 
@@ -23,42 +27,90 @@ pub struct EnemyHpBarRoot; // root to position over enemy
 #[derive(Component, Deref, DerefMut)]
 pub struct EnemySpeed(pub f32);
 
+// Elites and bosses are tougher, bigger and drop a treasure chest on death
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnemyRank {
+    #[default]
+    Normal,
+    Elite,
+    Boss,
+}
+
+impl EnemyRank {
+    pub fn hp_multiplier(self) -> f32 {
+        match self { EnemyRank::Normal => 1.0, EnemyRank::Elite => 5.0, EnemyRank::Boss => 25.0 }
+    }
+    pub fn size(self) -> f32 {
+        match self { EnemyRank::Normal => 32.0, EnemyRank::Elite => 48.0, EnemyRank::Boss => 80.0 }
+    }
+    pub fn tint(self) -> Color {
+        match self {
+            EnemyRank::Normal => Color::WHITE,
+            EnemyRank::Elite => Color::rgb(1.0, 0.85, 0.4),
+            EnemyRank::Boss => Color::rgb(0.8, 0.5, 1.0),
+        }
+    }
+    pub fn drops_chest(self) -> bool { self != EnemyRank::Normal }
+    pub fn xp_value(self) -> u32 {
+        match self { EnemyRank::Normal => 5, EnemyRank::Elite => 50, EnemyRank::Boss => 250 }
+    }
+}
+
+// bucket.png is a single frame for now; the death clip's length is how long a corpse stays before pooling
+pub const ENEMY_SHEET: SheetDef = SheetDef {
+    tile: UVec2::new(1024, 1536),
+    columns: 1,
+    rows: 1,
+    clips: &[
+        Clip { id: ClipId::Walk, first: 0, frames: 1, fps: 8.0, looping: Looping::Loop },
+        Clip { id: ClipId::Death, first: 0, frames: 1, fps: 10.0, looping: Looping::Once },
+    ],
+};
+
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, (
-            enemy_seek,
+            enemy_seek.after(FlowFieldSet),
             update_enemy_hp_bars,
         ).run_if(in_state(GameState::Playing)));
     }
 }
 
 fn enemy_seek(
-    mut enemies: Query<(&EnemySpeed, &mut Transform), With<Enemy>>,
+    mut enemies: Query<(&EnemySpeed, &StatusEffects, &mut Transform), With<Enemy>>,
     player: Query<&Transform, (With<Player>, Without<Enemy>)>,
     time: Res<Time>,
+    freeze: Res<EnemyFreeze>,
+    field: Res<FlowField>,
 ) {
+    if freeze.active() { return; }
     let Ok(player_tf) = player.get_single() else { return; };
-    for (speed, mut tf) in enemies.iter_mut() {
+    for (speed, status, mut tf) in enemies.iter_mut() {
+        // Slow / Freeze and friends
+        let speed = **speed * status.speed_multiplier();
         let to_player = (player_tf.translation - tf.translation).truncate();
         if to_player.length_squared() > 0.1 {
-            let dir = to_player.normalize();
-            tf.translation.x += dir.x * **speed * time.delta_seconds();
-            tf.translation.y += dir.y * **speed * time.delta_seconds();
+            // Route around obstacles via the shared flow field; straight chase when it has nothing to say
+            let dir = field.sample(tf.translation.truncate()).unwrap_or_else(|| to_player.normalize());
+            tf.translation.x += dir.x * speed * time.delta_seconds();
+            tf.translation.y += dir.y * speed * time.delta_seconds();
         }
     }
 }
 
 fn update_enemy_hp_bars(
-    enemies: Query<(&EnemyHealth, &Children), With<Enemy>>,
-    mut roots: Query<(&Children, &mut Transform), (With<EnemyHpBarRoot>, Without<EnemyHpBarFill>)>,
+    enemies: Query<(&EnemyHealth, &EnemyRank, &Children), With<Enemy>>,
+    mut roots: Query<(&Children, &mut Transform, &mut Visibility), (With<EnemyHpBarRoot>, Without<EnemyHpBarFill>)>,
     mut fills_tf: Query<&mut Transform, (With<EnemyHpBarFill>, Without<EnemyHpBarRoot>)>,
 ) {
-    for (health, enemy_children) in enemies.iter() {
+    for (health, rank, enemy_children) in enemies.iter() {
         let ratio = if health.max > 0.0 { (health.hp / health.max).clamp(0.0, 1.0) } else { 0.0 };
         // find bar root among enemy children
         for &child in enemy_children.iter() {
-            if let Ok((bar_children, mut bar_root_tf)) = roots.get_mut(child) {
-                bar_root_tf.translation.y = 20.0; // ensure stays above enemy
+            if let Ok((bar_children, mut bar_root_tf, mut bar_vis)) = roots.get_mut(child) {
+                bar_root_tf.translation.y = rank.size() * 0.5 + 4.0; // ensure stays above enemy
+                // Hidden while the previous occupant of a pooled entity played its death clip
+                bar_vis.set_if_neq(Visibility::Inherited);
                 for &bar_child in bar_children.iter() {
                     if let Ok(mut fill_tf) = fills_tf.get_mut(bar_child) {
                         let clamped = ratio.max(0.0);
@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+use super::enemy::Enemy;
+use super::player::{OrbitingFlame, Player, PlayerAura, FLAME_SHEET};
+use super::assets::PlayerAssets;
+use super::animation::{Animator, ClipId};
+use super::combat::AuraConfig;
+use super::status::{StatusEffects, StatusKind};
+use super::states::GameState;
+
+pub struct EvolutionPlugin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeaponKind {
+    OrbitingFlames,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PassiveKind {
+    // Granted by "+5 Aura Damage"
+    Kindling,
+    // Granted by "+20% Flame Speed"
+    Tailwind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvolvedWeapon {
+    DragonfireRing,
+}
+
+impl WeaponKind {
+    pub const ALL: [WeaponKind; 1] = [WeaponKind::OrbitingFlames];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            WeaponKind::OrbitingFlames => "weapon.orbiting_flames",
+        }
+    }
+}
+
+impl PassiveKind {
+    pub const ALL: [PassiveKind; 2] = [PassiveKind::Kindling, PassiveKind::Tailwind];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            PassiveKind::Kindling => "passive.kindling",
+            PassiveKind::Tailwind => "passive.tailwind",
+        }
+    }
+}
+
+impl EvolvedWeapon {
+    pub const ALL: [EvolvedWeapon; 1] = [EvolvedWeapon::DragonfireRing];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            EvolvedWeapon::DragonfireRing => "evolved.dragonfire_ring",
+        }
+    }
+}
+
+// Data-driven recipe: a max-level weapon plus an owned passive evolves into `result`
+pub struct EvolutionRecipe {
+    pub weapon: WeaponKind,
+    pub max_level: u32,
+    pub passive: PassiveKind,
+    pub result: EvolvedWeapon,
+    // Locale key; the display name is `result.label_key()`
+    pub description_key: &'static str,
+}
+
+pub const EVOLUTIONS: &[EvolutionRecipe] = &[
+    EvolutionRecipe {
+        weapon: WeaponKind::OrbitingFlames,
+        max_level: 5,
+        passive: PassiveKind::Kindling,
+        result: EvolvedWeapon::DragonfireRing,
+        description_key: "evolution.dragonfire_ring.desc",
+    },
+];
+
+// Weapons and passives the player has picked so far this run
+#[derive(Resource, Default)]
+pub struct Loadout {
+    pub weapon_levels: HashMap<WeaponKind, u32>,
+    pub passives: HashSet<PassiveKind>,
+    pub evolved: HashSet<EvolvedWeapon>,
+}
+
+impl Loadout {
+    pub fn weapon_level(&self, weapon: WeaponKind) -> u32 {
+        self.weapon_levels.get(&weapon).copied().unwrap_or(1)
+    }
+
+    pub fn level_up_weapon(&mut self, weapon: WeaponKind) {
+        let max = EVOLUTIONS.iter().filter(|r| r.weapon == weapon).map(|r| r.max_level).max().unwrap_or(u32::MAX);
+        let lvl = (self.weapon_level(weapon) + 1).min(max);
+        self.weapon_levels.insert(weapon, lvl);
+    }
+
+    pub fn is_eligible(&self, recipe: &EvolutionRecipe) -> bool {
+        !self.evolved.contains(&recipe.result)
+            && self.weapon_level(recipe.weapon) >= recipe.max_level
+            && self.passives.contains(&recipe.passive)
+    }
+
+    /// First recipe that can evolve right now, if any (chests and boss drops consult this).
+    pub fn eligible_evolution(&self) -> Option<&'static EvolutionRecipe> {
+        EVOLUTIONS.iter().find(|r| self.is_eligible(r))
+    }
+}
+
+// Fired by chest/boss rewards to transform a weapon
+#[derive(Event)]
+pub struct EvolveWeapon(pub EvolvedWeapon);
+
+// Marks flames that belong to the evolved ring (they set enemies fully ablaze on touch)
+#[derive(Component)]
+pub struct DragonfireRing;
+
+const DRAGONFIRE_FLAME_COUNT: usize = 16;
+const DRAGONFIRE_BURN_STACKS: u8 = 3;
+
+impl Plugin for EvolutionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Loadout>()
+            .add_event::<EvolveWeapon>()
+            .add_systems(Update, apply_evolutions)
+            .add_systems(Update, dragonfire_ignite.run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn apply_evolutions(
+    mut commands: Commands,
+    mut events: EventReader<EvolveWeapon>,
+    mut loadout: ResMut<Loadout>,
+    player_q: Query<Entity, With<Player>>,
+    flames_q: Query<(Entity, &OrbitingFlame)>,
+    cfg: Res<AuraConfig>,
+    player_assets: Res<PlayerAssets>,
+) {
+    for EvolveWeapon(evolved) in events.read() {
+        if !loadout.evolved.insert(*evolved) { continue; }
+        match evolved {
+            EvolvedWeapon::DragonfireRing => {
+                let Ok(player_entity) = player_q.get_single() else { continue; };
+                // Keep the current flame speed, then replace the loose flames with a dense ring
+                let speed = flames_q.iter().map(|(_, of)| of.speed).fold(1.8, f32::max);
+                for (e, _) in flames_q.iter() { commands.entity(e).despawn_recursive(); }
+                commands.entity(player_entity).with_children(|p| {
+                    for i in 0..DRAGONFIRE_FLAME_COUNT {
+                        let angle = i as f32 / DRAGONFIRE_FLAME_COUNT as f32 * std::f32::consts::TAU;
+                        p.spawn((PlayerAura, DragonfireRing, OrbitingFlame { angle, radius: cfg.radius, speed, contact_radius: 20.0, contact_damage: 8.0 }, Animator::new(&FLAME_SHEET, ClipId::Flicker), SpriteSheetBundle {
+                            texture: player_assets.flame.clone(),
+                            atlas: TextureAtlas { layout: player_assets.flame_layout.clone(), index: 0 },
+                            sprite: Sprite { color: Color::rgb(1.0, 0.6, 0.3), custom_size: Some(Vec2::splat(40.0)), ..default() },
+                            transform: Transform::from_xyz(angle.cos() * cfg.radius, angle.sin() * cfg.radius, 0.0),
+                            ..default()
+                        }));
+                    }
+                });
+            }
+        }
+    }
+}
+
+fn dragonfire_ignite(
+    ring: Query<(&GlobalTransform, &OrbitingFlame), With<DragonfireRing>>,
+    mut enemies: Query<(&Transform, &mut StatusEffects), With<Enemy>>,
+) {
+    if ring.is_empty() { return; }
+    let flames: Vec<(Vec2, f32)> = ring.iter().map(|(tf, of)| (tf.translation().truncate(), of.contact_radius)).collect();
+    for (tf, mut status) in enemies.iter_mut() {
+        let epos = tf.translation.truncate();
+        if flames.iter().any(|(fpos, r)| epos.distance(*fpos) < *r) {
+            status.apply(StatusKind::Burning, DRAGONFIRE_BURN_STACKS);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::map::MapObstacles;
+use super::player::Player;
+use super::states::GameState;
+
+pub struct FlowFieldPlugin;
+
+const CELL_SIZE: f32 = 32.0;
+const GRID_CELLS: i32 = 96;
+// Re-anchor the grid once the player is this many cells away from its centre
+const REANCHOR_CELLS: i32 = GRID_CELLS / 4;
+// Cells are blocked if an average enemy (this radius) would overlap an obstacle there
+const AGENT_RADIUS: f32 = 14.0;
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+// A full re-integration walks every cell, so a player crossing cells quickly triggers one at most this often
+const REINTEGRATE_SECONDS: f32 = 0.15;
+
+// Integration field around the player; every enemy samples its cell for an O(1) steering direction
+#[derive(Resource)]
+pub struct FlowField {
+    origin: Vec2,
+    blocked: Vec<bool>,
+    cost: Vec<u32>,
+    flow: Vec<Vec2>,
+    goal: Option<IVec2>,
+    // False when no obstacle falls inside the grid; enemies then just chase in a straight line
+    active: bool,
+    // Runs from the last integration; the goal may only follow the player once it has finished
+    cooldown: Timer,
+}
+
+impl Default for FlowField {
+    fn default() -> Self {
+        let n = (GRID_CELLS * GRID_CELLS) as usize;
+        Self { origin: Vec2::ZERO, blocked: vec![false; n], cost: vec![u32::MAX; n], flow: vec![Vec2::ZERO; n], goal: None, active: false, cooldown: Timer::from_seconds(REINTEGRATE_SECONDS, TimerMode::Once) }
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlowFieldSet;
+
+impl FlowField {
+    fn cell_of(&self, pos: Vec2) -> IVec2 {
+        ((pos - self.origin) / CELL_SIZE).floor().as_ivec2()
+    }
+
+    fn index(cell: IVec2) -> Option<usize> {
+        let in_grid = cell.x >= 0 && cell.y >= 0 && cell.x < GRID_CELLS && cell.y < GRID_CELLS;
+        in_grid.then(|| (cell.y * GRID_CELLS + cell.x) as usize)
+    }
+
+    /// Direction an enemy at `pos` should walk, or None to fall back to a straight chase
+    /// (field inactive, outside the grid, or already in the player's cell).
+    pub fn sample(&self, pos: Vec2) -> Option<Vec2> {
+        if !self.active { return None; }
+        let idx = Self::index(self.cell_of(pos))?;
+        let dir = self.flow[idx];
+        (dir != Vec2::ZERO).then_some(dir)
+    }
+
+    fn rebuild_blocked(&mut self, map: &MapObstacles) {
+        self.active = false;
+        for y in 0..GRID_CELLS {
+            for x in 0..GRID_CELLS {
+                let center = self.origin + (Vec2::new(x as f32, y as f32) + 0.5) * CELL_SIZE;
+                let blocked = map.blocked(center, AGENT_RADIUS);
+                self.blocked[(y * GRID_CELLS + x) as usize] = blocked;
+                self.active |= blocked;
+            }
+        }
+    }
+
+    // Dijkstra from the goal cell (8-connected, no corner cutting), then point each cell at its cheapest open neighbour
+    fn integrate(&mut self, goal: IVec2) {
+        self.goal = Some(goal);
+        self.cost.fill(u32::MAX);
+        self.flow.fill(Vec2::ZERO);
+        let Some(goal_idx) = Self::index(goal) else { return; };
+        let mut heap = BinaryHeap::new();
+        self.cost[goal_idx] = 0;
+        heap.push(Reverse((0u32, goal.x, goal.y)));
+        while let Some(Reverse((cost, x, y))) = heap.pop() {
+            let cell = IVec2::new(x, y);
+            let Some(ci) = Self::index(cell) else { continue; };
+            if cost > self.cost[ci] { continue; }
+            for (offset, step) in Self::neighbours(cell, &self.blocked) {
+                let next = cell + offset;
+                let Some(ni) = Self::index(next) else { continue; };
+                let nc = cost + step;
+                if nc < self.cost[ni] {
+                    self.cost[ni] = nc;
+                    heap.push(Reverse((nc, next.x, next.y)));
+                }
+            }
+        }
+        for y in 0..GRID_CELLS {
+            for x in 0..GRID_CELLS {
+                let cell = IVec2::new(x, y);
+                let idx = (y * GRID_CELLS + x) as usize;
+                // Blocked cells get a flow too: enemies pushed against an obstacle sit in them and must step back out
+                if cell == goal { continue; }
+                let best = Self::neighbours(cell, &self.blocked)
+                    .filter_map(|(offset, _)| Self::index(cell + offset).map(|ni| (offset, self.cost[ni])))
+                    .min_by_key(|(_, c)| *c);
+                if let Some((offset, c)) = best {
+                    if c < self.cost[idx] { self.flow[idx] = offset.as_vec2().normalize(); }
+                }
+            }
+        }
+    }
+
+    fn neighbours(cell: IVec2, blocked: &[bool]) -> impl Iterator<Item = (IVec2, u32)> + '_ {
+        let open = move |c: IVec2| Self::index(c).is_some_and(|i| !blocked[i]);
+        [
+            (IVec2::new(1, 0), STRAIGHT_COST), (IVec2::new(-1, 0), STRAIGHT_COST),
+            (IVec2::new(0, 1), STRAIGHT_COST), (IVec2::new(0, -1), STRAIGHT_COST),
+            (IVec2::new(1, 1), DIAGONAL_COST), (IVec2::new(1, -1), DIAGONAL_COST),
+            (IVec2::new(-1, 1), DIAGONAL_COST), (IVec2::new(-1, -1), DIAGONAL_COST),
+        ]
+        .into_iter()
+        .filter(move |(o, _)| {
+            // Diagonals need both adjacent straight cells open so enemies don't clip corners
+            open(cell + *o) && (o.x == 0 || o.y == 0 || (open(cell + IVec2::new(o.x, 0)) && open(cell + IVec2::new(0, o.y))))
+        })
+    }
+}
+
+impl Plugin for FlowFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlowField>()
+            .add_systems(Update, update_flow_field.in_set(FlowFieldSet).run_if(in_state(GameState::Playing)));
+    }
+}
+
+// Re-anchor (and re-rasterize obstacles) only when the player nears the grid edge. Each integration is a
+// full pass over the grid, so when the player changes cell it is re-run at most every REINTEGRATE_SECONDS;
+// in between enemies follow the previous goal, then chase directly once they reach it
+fn update_flow_field(
+    time: Res<Time>,
+    mut field: ResMut<FlowField>,
+    map: Res<MapObstacles>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let Ok(player_tf) = player.get_single() else { return; };
+    let ppos = player_tf.translation.truncate();
+    let half = IVec2::splat(GRID_CELLS / 2);
+    field.cooldown.tick(time.delta());
+    let cell = field.cell_of(ppos);
+    let off_center = (cell - half).abs().max_element() > REANCHOR_CELLS;
+    if off_center || field.goal.is_none() || map.is_changed() {
+        // Snap the origin to the cell grid so cells line up across re-anchors
+        let snapped = (ppos / CELL_SIZE).floor() * CELL_SIZE;
+        field.origin = snapped - half.as_vec2() * CELL_SIZE;
+        field.rebuild_blocked(&map);
+        field.goal = None;
+    }
+    let cell = field.cell_of(ppos);
+    // A re-anchor cleared the goal; the shifted grid needs integrating straight away
+    let due = field.goal.is_none() || field.cooldown.finished();
+    if field.active && field.goal != Some(cell) && due {
+        field.integrate(cell);
+        field.cooldown.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vertical wall at x = 48 spanning y = 30..66, goal to its right
+    fn walled_field() -> FlowField {
+        let mut field = FlowField::default();
+        for y in 30..66 {
+            field.blocked[(y * GRID_CELLS + 48) as usize] = true;
+        }
+        field.active = true;
+        field.integrate(IVec2::new(56, 48));
+        field
+    }
+
+    #[test]
+    fn path_goes_around_the_wall_without_cutting_corners() {
+        let field = walled_field();
+        let open = |c: IVec2| FlowField::index(c).is_some_and(|i| !field.blocked[i]);
+        let mut cell = IVec2::new(40, 48);
+        for _ in 0..200 {
+            if cell == IVec2::new(56, 48) { return; }
+            let dir = field.flow[FlowField::index(cell).unwrap()];
+            assert_ne!(dir, Vec2::ZERO, "stuck at {cell}");
+            let step = IVec2::new(dir.x.round() as i32, dir.y.round() as i32);
+            let next = cell + step;
+            assert!(open(next), "walked into the wall at {next}");
+            if step.x != 0 && step.y != 0 {
+                assert!(open(cell + IVec2::new(step.x, 0)) && open(cell + IVec2::new(0, step.y)), "cut a corner at {cell}");
+            }
+            cell = next;
+        }
+        panic!("never reached the goal");
+    }
+
+    #[test]
+    fn blocked_cells_point_back_to_open_ground() {
+        let field = walled_field();
+        let wall = IVec2::new(48, 48);
+        let dir = field.flow[FlowField::index(wall).unwrap()];
+        assert_ne!(dir, Vec2::ZERO);
+        let next = wall + IVec2::new(dir.x.round() as i32, dir.y.round() as i32);
+        assert!(!field.blocked[FlowField::index(next).unwrap()]);
+    }
+
+    #[test]
+    fn goal_follows_the_player_at_a_capped_rate() {
+        let mut app = App::new();
+        app.add_plugins(FlowFieldPlugin)
+            .insert_state(GameState::Playing)
+            .init_resource::<Time>()
+            .insert_resource(MapObstacles { boxes: vec![Rect::from_center_half_size(Vec2::new(200.0, 0.0), Vec2::splat(40.0))], bounds: None });
+        let player = app.world.spawn((Player, Transform::default())).id();
+        app.update();
+        let first = app.world.resource::<FlowField>().goal.expect("integrated on the first update");
+
+        let step = |app: &mut App, dx: f32, secs: f32| {
+            app.world.get_mut::<Transform>(player).unwrap().translation.x += dx;
+            app.world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs_f32(secs));
+            app.update();
+            app.world.resource::<FlowField>().goal.unwrap()
+        };
+        assert_eq!(step(&mut app, CELL_SIZE, 0.01), first, "a new cell inside the cooldown keeps the old goal");
+        assert_eq!(step(&mut app, CELL_SIZE, 0.01), first);
+        assert_eq!(step(&mut app, 0.0, REINTEGRATE_SECONDS), first + IVec2::new(2, 0), "caught up once the cooldown ran out");
+    }
+}
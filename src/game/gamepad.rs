@@ -0,0 +1,83 @@
+use bevy::input::gamepad::{GamepadAxisType, GamepadButtonType};
+use bevy::prelude::*;
+
+// Radial deadzone on the left stick; beyond it speed ramps from 0 to full at the rim
+pub const STICK_DEADZONE: f32 = 0.2;
+
+/// Left-stick direction of the first gamepad showing input, deadzoned and rescaled so magnitude
+/// goes 0..1 from the deadzone edge to full tilt. Zero when no pad is pushing the stick.
+pub fn left_stick(gamepads: &Gamepads, axes: &Axis<GamepadAxis>) -> Vec2 {
+    for pad in gamepads.iter() {
+        let x = axes.get(GamepadAxis::new(pad, GamepadAxisType::LeftStickX)).unwrap_or(0.0);
+        let y = axes.get(GamepadAxis::new(pad, GamepadAxisType::LeftStickY)).unwrap_or(0.0);
+        let raw = Vec2::new(x, y);
+        let len = raw.length();
+        if len > STICK_DEADZONE {
+            let scaled = ((len - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).min(1.0);
+            return raw / len * scaled;
+        }
+    }
+    Vec2::ZERO
+}
+
+/// True while `button` is held on any connected gamepad.
+pub fn pad_pressed(gamepads: &Gamepads, buttons: &ButtonInput<GamepadButton>, button: GamepadButtonType) -> bool {
+    gamepads.iter().any(|pad| buttons.pressed(GamepadButton::new(pad, button)))
+}
+
+/// True if `button` was just pressed on any connected gamepad.
+pub fn pad_just_pressed(gamepads: &Gamepads, buttons: &ButtonInput<GamepadButton>, button: GamepadButtonType) -> bool {
+    gamepads.iter().any(|pad| buttons.just_pressed(GamepadButton::new(pad, button)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo, GamepadAxisChangedEvent, GamepadButtonChangedEvent};
+    use bevy::input::InputPlugin;
+
+    fn app_with_pad() -> (App, Gamepad) {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+        let pad = Gamepad::new(0);
+        app.world.send_event(GamepadEvent::Connection(GamepadConnectionEvent::new(
+            pad,
+            GamepadConnection::Connected(GamepadInfo { name: "Synthetic Pad".into() }),
+        )));
+        app.update();
+        (app, pad)
+    }
+
+    fn stick(app: &App) -> Vec2 {
+        left_stick(app.world.resource::<Gamepads>(), app.world.resource::<Axis<GamepadAxis>>())
+    }
+
+    #[test]
+    fn stick_inside_deadzone_is_ignored() {
+        let (mut app, pad) = app_with_pad();
+        app.world.send_event(GamepadEvent::Axis(GamepadAxisChangedEvent::new(pad, GamepadAxisType::LeftStickX, 0.1)));
+        app.update();
+        assert_eq!(stick(&app), Vec2::ZERO);
+    }
+
+    #[test]
+    fn stick_is_analog_past_deadzone() {
+        let (mut app, pad) = app_with_pad();
+        app.world.send_event(GamepadEvent::Axis(GamepadAxisChangedEvent::new(pad, GamepadAxisType::LeftStickX, 0.6)));
+        app.update();
+        let half = stick(&app);
+        app.world.send_event(GamepadEvent::Axis(GamepadAxisChangedEvent::new(pad, GamepadAxisType::LeftStickX, 1.0)));
+        app.update();
+        let full = stick(&app);
+        assert!(half.x > 0.0 && half.x < full.x);
+        assert!((full.x - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn start_button_press_is_seen() {
+        let (mut app, pad) = app_with_pad();
+        app.world.send_event(GamepadEvent::Button(GamepadButtonChangedEvent::new(pad, GamepadButtonType::Start, 1.0)));
+        app.update();
+        assert!(pad_just_pressed(app.world.resource::<Gamepads>(), app.world.resource::<ButtonInput<GamepadButton>>(), GamepadButtonType::Start));
+    }
+}
@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::fmt::Display;
+
+use super::assets::UiFonts;
+use super::settings::Preferences;
+
+pub struct I18nPlugin;
+
+// Locale id and its strings file, embedded so web builds and tests need no asset loading.
+// The first entry is the reference locale and the fallback for missing keys.
+pub const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../../assets/locales/en.lang")),
+    ("fr", include_str!("../../assets/locales/fr.lang")),
+];
+
+// CLDR-style plural category; only the two our locales need
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plural {
+    One,
+    Other,
+}
+
+fn plural_category(locale: &str, n: i64) -> Plural {
+    match locale {
+        // French treats 0 and 1 as singular
+        "fr" => if n == 0 || n == 1 { Plural::One } else { Plural::Other },
+        _ => if n == 1 { Plural::One } else { Plural::Other },
+    }
+}
+
+/// Parse a `.lang` file: `key = value` per line, `#` comments and blank lines ignored.
+pub fn parse_lang(source: &str) -> HashMap<String, String> {
+    source.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect()
+}
+
+struct Locale {
+    id: &'static str,
+    strings: HashMap<String, String>,
+    // Every string is plain ASCII, so Bevy's built-in font can draw it
+    ascii: bool,
+}
+
+// All locales plus the active one; switch by changing `Preferences::language`
+#[derive(Resource)]
+pub struct Localization {
+    locales: Vec<Locale>,
+    current: usize,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        let locales = LOCALES.iter().map(|(id, src)| {
+            let strings = parse_lang(src);
+            let ascii = strings.values().all(|v| v.is_ascii());
+            Locale { id, strings, ascii }
+        }).collect();
+        Self { locales, current: 0 }
+    }
+}
+
+impl Localization {
+    pub fn set_language(&mut self, id: &str) {
+        if let Some(i) = self.locales.iter().position(|l| l.id == id) {
+            self.current = i;
+        }
+    }
+
+    pub fn language(&self) -> &'static str {
+        self.locales[self.current].id
+    }
+
+    /// Locales the UI can draw; only ASCII ones while the built-in font stands in for the body font.
+    pub fn languages(&self, ascii_only: bool) -> Vec<&'static str> {
+        self.locales.iter().filter(|l| l.ascii || !ascii_only).map(|l| l.id).collect()
+    }
+
+    // A locale's own name for itself ("Français"), for the language picker
+    pub fn language_name(&self, id: &str) -> String {
+        self.locales.iter().find(|l| l.id == id)
+            .and_then(|l| l.strings.get("language.name").cloned())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    // Active locale, then the reference locale, then the key itself so gaps are visible but harmless
+    fn raw<'a>(&'a self, key: &'a str) -> &'a str {
+        self.locales[self.current].strings.get(key)
+            .or_else(|| self.locales[0].strings.get(key))
+            .map_or(key, |s| s.as_str())
+    }
+
+    pub fn t(&self, key: &str) -> String {
+        self.raw(key).to_string()
+    }
+
+    /// Look up `key` and replace each `{name}` with its argument.
+    pub fn fmt(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut out = self.raw(key).to_string();
+        for (name, value) in args {
+            out = out.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        out
+    }
+
+    /// Pick `key.one` / `key.other` for `count` in the active language; `{count}` is bound automatically.
+    pub fn plural(&self, key: &str, count: i64, args: &[(&str, &dyn Display)]) -> String {
+        let suffix = match plural_category(self.language(), count) {
+            Plural::One => "one",
+            Plural::Other => "other",
+        };
+        let mut all: Vec<(&str, &dyn Display)> = vec![("count", &count)];
+        all.extend_from_slice(args);
+        self.fmt(&format!("{key}.{suffix}"), &all)
+    }
+}
+
+// Static UI text looked up by key; refreshed whenever the language changes
+#[derive(Component)]
+pub struct LocalizedText(pub &'static str);
+
+impl Plugin for I18nPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Localization>()
+            .add_systems(PreUpdate, sync_language)
+            .add_systems(Update, relocalize_texts);
+    }
+}
+
+// The preference is kept as chosen; without a font for its accents the reference locale shows instead
+fn sync_language(prefs: Res<Preferences>, fonts: Res<UiFonts>, mut loc: ResMut<Localization>) {
+    if !prefs.is_changed() && !fonts.is_changed() { return; }
+    let drawable = loc.languages(fonts.ascii_only()).contains(&prefs.language.as_str());
+    let wanted = if drawable { prefs.language.as_str() } else { LOCALES[0].0 };
+    if loc.language() != wanted {
+        loc.set_language(wanted);
+    }
+}
+
+fn relocalize_texts(
+    loc: Res<Localization>,
+    mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    for (key, mut text) in texts.iter_mut() {
+        if loc.is_changed() || key.is_added() {
+            text.sections[0].value = loc.t(key.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::evolution::{EvolvedWeapon, PassiveKind, WeaponKind, EVOLUTIONS};
+    use crate::game::input::{Action, ControlScheme};
+    use crate::game::upgrades::UPGRADES;
+
+    // Keys built from enums at runtime rather than written out in the UI code
+    fn generated_keys() -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        keys.extend(Action::ALL.iter().map(|a| a.l10n_key()));
+        keys.extend(ControlScheme::ALL.iter().map(|s| s.l10n_key()));
+        keys.extend(UPGRADES.iter().map(|u| u.label_key.to_string()));
+        keys.extend(WeaponKind::ALL.iter().map(|w| w.label_key().to_string()));
+        keys.extend(PassiveKind::ALL.iter().map(|p| p.label_key().to_string()));
+        keys.extend(EvolvedWeapon::ALL.iter().map(|e| e.label_key().to_string()));
+        keys.extend(EVOLUTIONS.iter().map(|r| r.description_key.to_string()));
+        keys
+    }
+
+    #[test]
+    fn every_locale_has_every_key() {
+        let reference = parse_lang(LOCALES[0].1);
+        let mut expected: Vec<String> = reference.keys().cloned().collect();
+        expected.extend(generated_keys());
+        for (id, source) in LOCALES {
+            let strings = parse_lang(source);
+            let missing: Vec<&String> = expected.iter().filter(|k| !strings.contains_key(*k)).collect();
+            assert!(missing.is_empty(), "locale '{id}' is missing keys: {missing:?}");
+            let extra: Vec<&String> = strings.keys().filter(|k| !reference.contains_key(*k)).collect();
+            assert!(extra.is_empty(), "locale '{id}' has keys unknown to '{}': {extra:?}", LOCALES[0].0);
+        }
+    }
+
+    #[test]
+    fn interpolation_and_plurals() {
+        let mut loc = Localization::default();
+        assert_eq!(loc.fmt("hud.hp", &[("hp", &40), ("max", &100)]), "HP: 40 / 100");
+        assert_eq!(loc.plural("chest.title", 1, &[]), "Treasure! 1 reward");
+        assert_eq!(loc.plural("chest.title", 0, &[]), "Treasure! 0 rewards");
+        loc.set_language("fr");
+        assert_eq!(loc.plural("chest.title", 0, &[]), "Trésor ! 0 récompense");
+        assert_eq!(loc.languages(true), vec!["en"], "accented locales need a real font");
+        assert_eq!(loc.t("no.such.key"), "no.such.key");
+    }
+}
@@ -0,0 +1,322 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::GamepadButtonType;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+use super::gamepad::{left_stick, pad_just_pressed, pad_pressed};
+use super::touch::VirtualStick;
+
+pub struct InputMapPlugin;
+
+// Everything gameplay and menus react to; devices are mapped onto these instead of read directly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Pause,
+    Confirm,
+    Restart,
+    MenuUp,
+    MenuDown,
+    Fullscreen,
+    Codex,
+    Controls,
+    Settings,
+}
+
+impl Action {
+    pub const ALL: [Action; 13] = [
+        Action::MoveUp, Action::MoveDown, Action::MoveLeft, Action::MoveRight,
+        Action::Pause, Action::Confirm, Action::Restart, Action::MenuUp, Action::MenuDown,
+        Action::Fullscreen, Action::Codex, Action::Controls, Action::Settings,
+    ];
+
+    // Stable identifier used in the settings file
+    pub fn id(self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::Pause => "pause",
+            Action::Confirm => "confirm",
+            Action::Restart => "restart",
+            Action::MenuUp => "menu_up",
+            Action::MenuDown => "menu_down",
+            Action::Fullscreen => "fullscreen",
+            Action::Codex => "codex",
+            Action::Controls => "controls",
+            Action::Settings => "settings",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    // Menu navigation deliberately shares keys with movement, so the two only clash among themselves
+    fn menu_nav(self) -> bool {
+        matches!(self, Action::MenuUp | Action::MenuDown)
+    }
+
+    // Locale key for the action's display name
+    pub fn l10n_key(self) -> String {
+        format!("action.{}", self.id())
+    }
+}
+
+// How the mouse/pointer participates in steering; persisted in settings
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlScheme {
+    // Dragon walks toward the cursor whenever no move key is held
+    PointerFollow,
+    // Walk toward the cursor only while the left button is held
+    HoldToMove,
+    // Mouse is ignored for movement
+    Keyboard,
+    // Move with keys, aim with the mouse
+    TwinStick,
+}
+
+impl Default for ControlScheme {
+    // Browser builds are usually played mouse-first; desktop builds keyboard-first
+    fn default() -> Self {
+        if cfg!(target_arch = "wasm32") { ControlScheme::PointerFollow } else { ControlScheme::Keyboard }
+    }
+}
+
+impl ControlScheme {
+    pub const ALL: [ControlScheme; 4] = [
+        ControlScheme::PointerFollow, ControlScheme::HoldToMove, ControlScheme::Keyboard, ControlScheme::TwinStick,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            ControlScheme::PointerFollow => "pointer_follow",
+            ControlScheme::HoldToMove => "hold_to_move",
+            ControlScheme::Keyboard => "keyboard",
+            ControlScheme::TwinStick => "twin_stick",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<ControlScheme> {
+        ControlScheme::ALL.into_iter().find(|s| s.id() == id)
+    }
+
+    pub fn l10n_key(self) -> String {
+        format!("scheme.{}", self.id())
+    }
+
+    pub fn next(self) -> ControlScheme {
+        let i = ControlScheme::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        ControlScheme::ALL[(i + 1) % ControlScheme::ALL.len()]
+    }
+}
+
+// Keys offered for rebinding; also the lookup table for parsing saved bindings
+pub const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE, KeyCode::KeyF, KeyCode::KeyG,
+    KeyCode::KeyH, KeyCode::KeyI, KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL, KeyCode::KeyM, KeyCode::KeyN,
+    KeyCode::KeyO, KeyCode::KeyP, KeyCode::KeyQ, KeyCode::KeyR, KeyCode::KeyS, KeyCode::KeyT, KeyCode::KeyU,
+    KeyCode::KeyV, KeyCode::KeyW, KeyCode::KeyX, KeyCode::KeyY, KeyCode::KeyZ,
+    KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+    KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+    KeyCode::ArrowUp, KeyCode::ArrowDown, KeyCode::ArrowLeft, KeyCode::ArrowRight,
+    KeyCode::Space, KeyCode::Enter, KeyCode::Escape, KeyCode::Tab, KeyCode::Backspace,
+    KeyCode::ShiftLeft, KeyCode::ShiftRight, KeyCode::ControlLeft, KeyCode::ControlRight,
+    KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+    KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+];
+
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
+pub fn key_from_name(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS.iter().copied().find(|k| key_name(*k) == name)
+}
+
+// Device -> action bindings. Keys are rebindable (and persisted via settings); pad buttons are fixed.
+#[derive(Resource, Clone)]
+pub struct InputMap {
+    pub keys: HashMap<Action, Vec<KeyCode>>,
+    pub pad: HashMap<Action, Vec<GamepadButtonType>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use GamepadButtonType as Pad;
+        let keys = HashMap::from_iter([
+            (Action::MoveUp, vec![KeyCode::KeyW, KeyCode::ArrowUp]),
+            (Action::MoveDown, vec![KeyCode::KeyS, KeyCode::ArrowDown]),
+            (Action::MoveLeft, vec![KeyCode::KeyA, KeyCode::ArrowLeft]),
+            (Action::MoveRight, vec![KeyCode::KeyD, KeyCode::ArrowRight]),
+            (Action::Pause, vec![KeyCode::Escape]),
+            (Action::Confirm, vec![KeyCode::Enter, KeyCode::Space]),
+            (Action::Restart, vec![KeyCode::KeyR]),
+            (Action::MenuUp, vec![KeyCode::ArrowUp, KeyCode::KeyW]),
+            (Action::MenuDown, vec![KeyCode::ArrowDown, KeyCode::KeyS]),
+            (Action::Fullscreen, vec![KeyCode::F11, KeyCode::KeyF]),
+            (Action::Codex, vec![KeyCode::KeyC]),
+            (Action::Controls, vec![KeyCode::KeyK]),
+            (Action::Settings, vec![KeyCode::KeyO]),
+        ]);
+        let pad = HashMap::from_iter([
+            (Action::MoveUp, vec![Pad::DPadUp]),
+            (Action::MoveDown, vec![Pad::DPadDown]),
+            (Action::MoveLeft, vec![Pad::DPadLeft]),
+            (Action::MoveRight, vec![Pad::DPadRight]),
+            (Action::Pause, vec![Pad::Start]),
+            (Action::Confirm, vec![Pad::South]),
+            // (A) confirms the focused Game Over button instead
+            (Action::Restart, vec![Pad::Start]),
+            (Action::MenuUp, vec![Pad::DPadUp]),
+            (Action::MenuDown, vec![Pad::DPadDown]),
+            (Action::Codex, vec![Pad::North]),
+            (Action::Controls, vec![Pad::Select]),
+        ]);
+        Self { keys, pad }
+    }
+}
+
+impl InputMap {
+    pub fn keys_for(&self, action: Action) -> &[KeyCode] {
+        self.keys.get(&action).map_or(&[], |v| v.as_slice())
+    }
+
+    /// Make `key` the primary binding of `action`. Another action that already used `key` gets
+    /// `action`'s old primary in its place (swap), so two actions never silently share a key.
+    pub fn rebind(&mut self, action: Action, key: KeyCode) {
+        let old = self.keys_for(action).first().copied();
+        if old == Some(key) { return; }
+        for (other, keys) in self.keys.iter_mut() {
+            if *other == action || other.menu_nav() != action.menu_nav() { continue; }
+            let Some(slot) = keys.iter().position(|k| *k == key) else { continue; };
+            match old {
+                Some(old) if !keys.contains(&old) => keys[slot] = old,
+                _ => { keys.remove(slot); }
+            }
+        }
+        let keys = self.keys.entry(action).or_default();
+        keys.retain(|k| *k != key);
+        match keys.first_mut() {
+            Some(primary) if Some(*primary) == old => *primary = key,
+            _ => keys.insert(0, key),
+        }
+    }
+}
+
+// Per-frame action state, filled in PreUpdate from keyboard, gamepad, the virtual stick and touch buttons
+#[derive(Resource, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    // Digital move keys/D-pad combined with the analog (pad or virtual) stick; length <= 1
+    pub move_axis: Vec2,
+    // True when move_axis came from an analog stick (partial tilt = partial speed)
+    pub move_is_analog: bool,
+    // Presses from on-screen/touch buttons, folded into `just_pressed` on the next update
+    virtual_presses: Vec<Action>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool { self.pressed.contains(&action) }
+    pub fn just_pressed(&self, action: Action) -> bool { self.just_pressed.contains(&action) }
+
+    /// Queue a press from a virtual (touch/UI) button; it is visible as `just_pressed` next frame.
+    pub fn press_virtual(&mut self, action: Action) { self.virtual_presses.push(action); }
+}
+
+// Action waiting for its next key press in the rebinding UI; while set, actions are suppressed
+#[derive(Resource, Default)]
+pub struct RebindListening(pub Option<Action>);
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionSet;
+
+impl Plugin for InputMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .init_resource::<ActionState>()
+            .init_resource::<ControlScheme>()
+            .init_resource::<RebindListening>()
+            .add_systems(PreUpdate, update_action_state.in_set(ActionSet).after(InputSystem));
+    }
+}
+
+// Every raw device the action map reads from
+#[derive(SystemParam)]
+struct InputSources<'w> {
+    kb: Res<'w, ButtonInput<KeyCode>>,
+    gamepads: Res<'w, Gamepads>,
+    pad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    pad_axes: Res<'w, Axis<GamepadAxis>>,
+    virtual_stick: Res<'w, VirtualStick>,
+}
+
+fn update_action_state(
+    map: Res<InputMap>,
+    listening: Res<RebindListening>,
+    input: InputSources,
+    mut state: ResMut<ActionState>,
+) {
+    let InputSources { kb, gamepads, pad_buttons, pad_axes, virtual_stick } = input;
+    state.pressed.clear();
+    state.just_pressed.clear();
+    state.move_axis = Vec2::ZERO;
+    state.move_is_analog = false;
+    let virtual_presses = std::mem::take(&mut state.virtual_presses);
+    if listening.0.is_some() { return; }
+    state.just_pressed.extend(virtual_presses);
+
+    for action in Action::ALL {
+        let keys = map.keys_for(action);
+        let pads = map.pad.get(&action).map_or(&[][..], |v| v.as_slice());
+        if kb.any_pressed(keys.iter().copied()) || pads.iter().any(|b| pad_pressed(&gamepads, &pad_buttons, *b)) {
+            state.pressed.insert(action);
+        }
+        if kb.any_just_pressed(keys.iter().copied()) || pads.iter().any(|b| pad_just_pressed(&gamepads, &pad_buttons, *b)) {
+            state.just_pressed.insert(action);
+        }
+    }
+
+    let mut stick = left_stick(&gamepads, &pad_axes);
+    if stick == Vec2::ZERO { stick = virtual_stick.axis; }
+    if stick != Vec2::ZERO {
+        state.move_axis = stick;
+        state.move_is_analog = true;
+    } else {
+        let mut dir = Vec2::ZERO;
+        if state.pressed(Action::MoveUp) { dir.y += 1.0; }
+        if state.pressed(Action::MoveDown) { dir.y -= 1.0; }
+        if state.pressed(Action::MoveLeft) { dir.x -= 1.0; }
+        if state.pressed(Action::MoveRight) { dir.x += 1.0; }
+        state.move_axis = dir.normalize_or_zero();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_to_a_used_key_swaps_bindings() {
+        let mut map = InputMap::default();
+        // C belongs to the Codex; taking it for Restart hands R over to the Codex
+        map.rebind(Action::Restart, KeyCode::KeyC);
+        assert_eq!(map.keys_for(Action::Restart), &[KeyCode::KeyC]);
+        assert_eq!(map.keys_for(Action::Codex), &[KeyCode::KeyR]);
+        // Menu navigation may keep sharing keys with movement
+        map.rebind(Action::MoveUp, KeyCode::KeyZ);
+        assert_eq!(map.keys_for(Action::MoveUp), &[KeyCode::KeyZ, KeyCode::ArrowUp]);
+        assert_eq!(map.keys_for(Action::MenuUp), &[KeyCode::ArrowUp, KeyCode::KeyW]);
+        for (action, keys) in &map.keys {
+            for other in Action::ALL.iter().filter(|o| **o != *action && o.menu_nav() == action.menu_nav()) {
+                assert!(keys.iter().all(|k| !map.keys_for(*other).contains(k)), "{action:?} and {other:?} share a key");
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+
+use super::camera::CameraRig;
+use super::states::GameState;
+
+pub struct MapPlugin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObstacleKind {
+    Rock,
+    Ruin,
+    DragonBones,
+}
+
+impl ObstacleKind {
+    fn color(self) -> Color {
+        match self {
+            ObstacleKind::Rock => Color::rgb(0.35, 0.33, 0.32),
+            ObstacleKind::Ruin => Color::rgb(0.45, 0.38, 0.30),
+            ObstacleKind::DragonBones => Color::rgb(0.85, 0.82, 0.72),
+        }
+    }
+}
+
+pub struct ObstacleDef {
+    pub kind: ObstacleKind,
+    pub center: Vec2,
+    pub half_size: Vec2,
+}
+
+// Stage layout: solid obstacles plus optional arena bounds (None = endless)
+pub struct StageDef {
+    pub name: &'static str,
+    pub obstacles: &'static [ObstacleDef],
+    pub bounds: Option<Rect>,
+}
+
+const fn obstacle(kind: ObstacleKind, x: f32, y: f32, hw: f32, hh: f32) -> ObstacleDef {
+    ObstacleDef { kind, center: Vec2::new(x, y), half_size: Vec2::new(hw, hh) }
+}
+
+pub const STAGES: &[StageDef] = &[
+    StageDef {
+        name: "Scorched Plains",
+        obstacles: &[
+            obstacle(ObstacleKind::Rock, 420.0, 180.0, 60.0, 50.0),
+            obstacle(ObstacleKind::Rock, -380.0, -260.0, 70.0, 55.0),
+            obstacle(ObstacleKind::Ruin, 0.0, 520.0, 220.0, 30.0),
+            obstacle(ObstacleKind::Ruin, 0.0, -560.0, 220.0, 30.0),
+            obstacle(ObstacleKind::DragonBones, -700.0, 300.0, 40.0, 160.0),
+            obstacle(ObstacleKind::DragonBones, 760.0, -320.0, 40.0, 160.0),
+            obstacle(ObstacleKind::Rock, 1100.0, 600.0, 90.0, 80.0),
+            obstacle(ObstacleKind::Ruin, -1200.0, -700.0, 30.0, 240.0),
+        ],
+        bounds: None,
+    },
+    StageDef {
+        name: "Bone Pit",
+        obstacles: &[
+            obstacle(ObstacleKind::DragonBones, -300.0, 0.0, 30.0, 200.0),
+            obstacle(ObstacleKind::DragonBones, 300.0, 0.0, 30.0, 200.0),
+            obstacle(ObstacleKind::Rock, 0.0, 350.0, 80.0, 40.0),
+            obstacle(ObstacleKind::Rock, 0.0, -350.0, 80.0, 40.0),
+        ],
+        bounds: Some(Rect { min: Vec2::new(-900.0, -600.0), max: Vec2::new(900.0, 600.0) }),
+    },
+];
+
+#[derive(Resource, Default)]
+pub struct CurrentStage(pub usize);
+
+impl CurrentStage {
+    pub fn def(&self) -> &'static StageDef { &STAGES[self.0.min(STAGES.len() - 1)] }
+}
+
+#[derive(Component)]
+pub struct Obstacle;
+
+// Rings `free_spot_near` tries before giving up
+const FREE_SPOT_RINGS: usize = 16;
+
+// Collision shapes of the loaded stage (axis-aligned boxes) plus its bounds
+#[derive(Resource, Default)]
+pub struct MapObstacles {
+    pub boxes: Vec<Rect>,
+    pub bounds: Option<Rect>,
+}
+
+impl MapObstacles {
+    /// True when a circle at `pos` overlaps any obstacle or leaves the arena.
+    pub fn blocked(&self, pos: Vec2, radius: f32) -> bool {
+        let overlaps = self.boxes.iter().any(|rect| pos.distance_squared(pos.clamp(rect.min, rect.max)) < radius * radius);
+        let outside = self.bounds.is_some_and(|b| pos != pos.clamp(b.min + Vec2::splat(radius), b.max - Vec2::splat(radius)));
+        overlaps || outside
+    }
+
+    /// Pushes a circle out of every obstacle (and back into bounds), returning the corrected centre.
+    pub fn resolve_circle(&self, mut pos: Vec2, radius: f32) -> Vec2 {
+        for rect in &self.boxes {
+            let closest = pos.clamp(rect.min, rect.max);
+            let delta = pos - closest;
+            let dist_sq = delta.length_squared();
+            if dist_sq >= radius * radius { continue; }
+            if dist_sq > 1e-6 {
+                pos = closest + delta / dist_sq.sqrt() * radius;
+            } else {
+                // Centre is inside the box: exit through the nearest face
+                let to_min = pos - rect.min;
+                let to_max = rect.max - pos;
+                let exits = [
+                    (to_min.x, Vec2::new(rect.min.x - radius, pos.y)),
+                    (to_max.x, Vec2::new(rect.max.x + radius, pos.y)),
+                    (to_min.y, Vec2::new(pos.x, rect.min.y - radius)),
+                    (to_max.y, Vec2::new(pos.x, rect.max.y + radius)),
+                ];
+                pos = exits.iter().min_by(|a, b| a.0.total_cmp(&b.0)).map_or(pos, |e| e.1);
+            }
+        }
+        if let Some(b) = self.bounds {
+            pos = pos.clamp(b.min + Vec2::splat(radius), b.max - Vec2::splat(radius));
+        }
+        pos
+    }
+
+    /// Nearest free spot for a circle, searching outward from `pos` in rings (used by spawners).
+    /// None when nothing within `FREE_SPOT_RINGS` rings is clear; callers skip that spawn.
+    pub fn free_spot_near(&self, pos: Vec2, radius: f32) -> Option<Vec2> {
+        // Start from the pushed-out position so points outside a bounded arena come back in first
+        let start = self.resolve_circle(pos, radius);
+        if !self.blocked(start, radius) { return Some(start); }
+        let step = radius.max(8.0);
+        for ring in 1..=FREE_SPOT_RINGS {
+            let dist = ring as f32 * step;
+            let samples = 8 * ring;
+            for i in 0..samples {
+                let candidate = start + Vec2::from_angle(i as f32 / samples as f32 * std::f32::consts::TAU) * dist;
+                if !self.blocked(candidate, radius) { return Some(candidate); }
+            }
+        }
+        None
+    }
+}
+
+impl Plugin for MapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CurrentStage>()
+            .init_resource::<MapObstacles>()
+            .add_systems(OnEnter(GameState::Playing), load_stage);
+    }
+}
+
+fn load_stage(
+    mut commands: Commands,
+    stage: Res<CurrentStage>,
+    mut map: ResMut<MapObstacles>,
+    mut rig: ResMut<CameraRig>,
+    existing: Query<Entity, With<Obstacle>>,
+) {
+    // Returning from Pause/LevelUp keeps the already loaded stage
+    if !existing.is_empty() && !stage.is_changed() { return; }
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+    let def = stage.def();
+    info!("Loading stage: {}", def.name);
+    map.boxes.clear();
+    map.bounds = def.bounds;
+    rig.bounds = def.bounds;
+    for o in def.obstacles {
+        map.boxes.push(Rect::from_center_half_size(o.center, o.half_size));
+        commands.spawn((Obstacle, SpriteBundle {
+            sprite: Sprite { color: o.kind.color(), custom_size: Some(o.half_size * 2.0), ..default() },
+            transform: Transform::from_xyz(o.center.x, o.center.y, 0.2),
+            ..default()
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_spot_escapes_a_narrow_gap() {
+        // Two boxes 20 apart: pushing out of one lands inside the other
+        let map = MapObstacles {
+            boxes: vec![
+                Rect::from_center_half_size(Vec2::new(-60.0, 0.0), Vec2::new(50.0, 50.0)),
+                Rect::from_center_half_size(Vec2::new(60.0, 0.0), Vec2::new(50.0, 50.0)),
+            ],
+            bounds: None,
+        };
+        let spot = map.free_spot_near(Vec2::ZERO, 16.0).expect("open ground above and below");
+        assert!(!map.blocked(spot, 16.0));
+
+        // A bounded arena filled by one box has no free spot at all
+        let walled_in = MapObstacles {
+            boxes: vec![Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(600.0))],
+            bounds: Some(Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(500.0))),
+        };
+        assert_eq!(walled_in.free_spot_near(Vec2::ZERO, 16.0), None);
+    }
+}
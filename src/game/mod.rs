@@ -1,4 +1,5 @@
 pub mod states;
+pub mod animation;
 pub mod player;
 pub mod enemy;
 pub mod spawn;
@@ -6,9 +7,27 @@ pub mod movement;
 pub mod xp;
 pub mod ui;
 pub mod assets;
+pub mod audio;
 pub mod combat;
+pub mod status;
+pub mod damage_numbers;
+pub mod particles;
+pub mod evolution;
+pub mod upgrades;
+pub mod chest;
+pub mod pickups;
+pub mod camera;
+pub mod background;
+pub mod map;
+pub mod flowfield;
+pub mod gamepad;
+pub mod i18n;
+pub mod input;
+pub mod settings;
+pub mod touch;
 
 use bevy::prelude::*;
+use bevy::window::WindowFocused;
 #[cfg(target_arch = "wasm32")]
 use bevy::window::{PrimaryWindow, WindowMode};
 #[cfg(target_arch = "wasm32")]
@@ -16,11 +35,25 @@ use web_sys::window as web_window;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::JsCast;
 use states::*;
+use input::{Action, ActionState};
 
 pub struct GamePlugin;
+
+// Start a fresh run (the Restart menu button; on Game Over the Restart action works too)
+#[derive(Event)]
+pub struct RestartRun;
+
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
+            .add_event::<RestartRun>()
+            // Input actions, touch controls, persisted settings & localization
+            .add_plugins((
+                input::InputMapPlugin,
+                touch::TouchControlsPlugin,
+                settings::SettingsPlugin,
+                i18n::I18nPlugin,
+            ))
             .add_plugins((
                 assets::AssetsPlugin,
                 player::PlayerPlugin,
@@ -28,22 +61,37 @@ impl Plugin for GamePlugin {
                 spawn::SpawnPlugin,
                 movement::MovementPlugin,
                 combat::CombatPlugin,
+                evolution::EvolutionPlugin,
+                upgrades::UpgradesPlugin,
+                chest::ChestPlugin,
+                pickups::PickupsPlugin,
+                camera::CameraPlugin,
+                background::BackgroundPlugin,
+                map::MapPlugin,
                 xp::XpPlugin,
                 ui::UiPlugin,
+            ))
+            .add_plugins((
+                flowfield::FlowFieldPlugin,
+                audio::GameAudioPlugin,
+                damage_numbers::DamageNumbersPlugin,
+                status::StatusPlugin,
+                animation::AnimationPlugin,
+                particles::ParticlesPlugin,
             ));
         // Input driven state toggles
         app.add_systems(Update, (
             toggle_pause,
+            auto_pause_on_focus_loss,
             restart_game,
-            auto_start_loading,
             #[cfg(target_arch = "wasm32")] resize_canvas_to_window,
             #[cfg(target_arch = "wasm32")] toggle_fullscreen,
         ));
     }
 }
 
-fn toggle_pause(kb: Res<ButtonInput<KeyCode>>, state: Res<State<GameState>>, mut next: ResMut<NextState<GameState>>) {
-    if kb.just_pressed(KeyCode::Escape) {
+fn toggle_pause(actions: Res<ActionState>, state: Res<State<GameState>>, mut next: ResMut<NextState<GameState>>) {
+    if actions.just_pressed(Action::Pause) {
         match state.get() {
             GameState::Playing => next.set(GameState::Paused),
             GameState::Paused => next.set(GameState::Playing),
@@ -52,22 +100,50 @@ fn toggle_pause(kb: Res<ButtonInput<KeyCode>>, state: Res<State<GameState>>, mut
     }
 }
 
-fn restart_game(kb: Res<ButtonInput<KeyCode>>, state: Res<State<GameState>>, mut next: ResMut<NextState<GameState>>, mut stats: ResMut<player::PlayerStats>, mut q_player: Query<&mut Transform, With<player::Player>>) {
-    if kb.just_pressed(KeyCode::KeyR) && matches!(state.get(), GameState::GameOver) {
-        // Reset player stats & position
-        *stats = player::PlayerStats { level: 1, max_hp: 100.0, hp: 100.0, xp: 0 };
-        if let Some(mut tf) = q_player.iter_mut().next() { tf.translation = Vec3::ZERO; }
-        next.set(GameState::Playing);
+// Window / browser tab lost focus mid-run: pause so the player doesn't come back to a dead dragon
+fn auto_pause_on_focus_loss(mut focus: EventReader<WindowFocused>, state: Res<State<GameState>>, mut next: ResMut<NextState<GameState>>) {
+    if focus.read().any(|ev| !ev.focused) && matches!(state.get(), GameState::Playing) {
+        next.set(GameState::Paused);
     }
 }
 
-fn auto_start_loading(state: Res<State<GameState>>, mut next: ResMut<NextState<GameState>>) {
-    if matches!(state.get(), GameState::Loading) {
-        // Immediately transition to Playing (placeholder for future asset gating)
+fn restart_game(mut commands: Commands, actions: Res<ActionState>, mut requests: EventReader<RestartRun>, state: Res<State<GameState>>, mut next: ResMut<NextState<GameState>>) {
+    let requested = requests.read().count() > 0;
+    if requested || (actions.just_pressed(Action::Restart) && matches!(state.get(), GameState::GameOver)) {
+        commands.add(reset_run);
         next.set(GameState::Playing);
     }
 }
 
+// Throw away everything the last run built up; entering Playing then spawns a fresh player
+fn reset_run(world: &mut World) {
+    fn despawn_all<T: Component>(world: &mut World) {
+        let entities: Vec<Entity> = world.query_filtered::<Entity, With<T>>().iter(world).collect();
+        for e in entities { despawn_with_children_recursive(world, e); }
+    }
+    despawn_all::<player::Player>(world);
+    despawn_all::<enemy::Enemy>(world);
+    despawn_all::<xp::XpOrb>(world);
+    despawn_all::<chest::Chest>(world);
+    despawn_all::<pickups::Pickup>(world);
+    despawn_all::<spawn::Dying>(world);
+    despawn_all::<particles::Particle>(world);
+    despawn_all::<damage_numbers::DamageNumber>(world);
+
+    world.insert_resource(player::PlayerStats::default());
+    world.insert_resource(xp::PendingLevelUps::default());
+    world.insert_resource(evolution::Loadout::default());
+    world.insert_resource(combat::AuraConfig::default());
+    world.insert_resource(combat::AuraTickTimer::default());
+    world.insert_resource(upgrades::FlameSpeedBuff(1.0));
+    world.insert_resource(spawn::EnemySpawnTimer::default());
+    world.insert_resource(pickups::EnemyFreeze::default());
+    world.insert_resource(chest::ChestReward::default());
+    // No goal: the next update re-anchors on the new player and re-integrates
+    world.insert_resource(flowfield::FlowField::default());
+    world.resource_mut::<camera::CameraRig>().recenter();
+}
+
 // -- Web-only helpers to make the game fill the browser and support fullscreen --
 #[cfg(target_arch = "wasm32")]
 fn resize_canvas_to_window(
@@ -97,10 +173,10 @@ fn resize_canvas_to_window(
 
 #[cfg(target_arch = "wasm32")]
 fn toggle_fullscreen(
-    kb: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     mut windows: Query<&mut bevy::window::Window, With<PrimaryWindow>>,
 ) {
-    if kb.just_pressed(KeyCode::F11) || kb.just_pressed(KeyCode::KeyF) {
+    if actions.just_pressed(Action::Fullscreen) {
         if let Ok(mut win) = windows.get_single_mut() {
             win.mode = if matches!(win.mode, WindowMode::Fullscreen) {
                 WindowMode::Windowed
@@ -1,4 +1,39 @@
 use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+use super::camera::CameraFollowSet;
+use super::enemy::{Enemy, EnemyRank};
+use super::map::MapObstacles;
+use super::player::Player;
 
 pub struct MovementPlugin;
-impl Plugin for MovementPlugin { fn build(&self, _app: &mut App) {} }
+
+// Collision radius of the dragon against map obstacles
+pub const PLAYER_RADIUS: f32 = 18.0;
+
+impl Plugin for MovementPlugin {
+    fn build(&self, app: &mut App) {
+        // Resolve after all Update movement but before the camera reads the player position
+        app.add_systems(PostUpdate, resolve_obstacle_collisions
+            .before(CameraFollowSet)
+            .before(TransformSystem::TransformPropagate));
+    }
+}
+
+fn resolve_obstacle_collisions(
+    map: Res<MapObstacles>,
+    mut player: Query<&mut Transform, (With<Player>, Without<Enemy>)>,
+    mut enemies: Query<(&EnemyRank, &mut Transform), With<Enemy>>,
+) {
+    if map.boxes.is_empty() && map.bounds.is_none() { return; }
+    for mut tf in player.iter_mut() {
+        let pos = map.resolve_circle(tf.translation.truncate(), PLAYER_RADIUS);
+        tf.translation.x = pos.x;
+        tf.translation.y = pos.y;
+    }
+    for (rank, mut tf) in enemies.iter_mut() {
+        let pos = map.resolve_circle(tf.translation.truncate(), rank.size() * 0.4);
+        tf.translation.x = pos.x;
+        tf.translation.y = pos.y;
+    }
+}
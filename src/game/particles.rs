@@ -0,0 +1,237 @@
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use rand::Rng;
+
+use super::combat::CombatSet;
+use super::player::OrbitingFlame;
+use super::states::GameState;
+
+pub struct ParticlesPlugin;
+
+// Live particle cap at a healthy frame rate, and the floor it shrinks to when FPS tanks
+const MAX_PARTICLES: usize = 600;
+const MIN_PARTICLES: usize = 60;
+// FPS range over which the budget scales between the floor and the cap
+const LOW_FPS: f64 = 30.0;
+const HIGH_FPS: f64 = 55.0;
+const PARTICLE_Z: f32 = 4.0;
+
+/// How one effect spawns and evolves; each particle is a plain square tinted and sized over its life.
+pub struct EmitterDef {
+    // Particles per second for continuous emitters (bursts give an explicit count)
+    pub rate: f32,
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+    // Centre of the velocity cone and its half-angle in radians (PI = every direction)
+    pub direction: Vec2,
+    pub spread: f32,
+    // Fraction of velocity lost per second
+    pub drag: f32,
+    pub color: (Color, Color),
+    pub size: (f32, f32),
+}
+
+// Short-lived embers left behind each orbiting flame
+pub const FLAME_TRAIL: EmitterDef = EmitterDef {
+    rate: 18.0,
+    lifetime: (0.25, 0.45),
+    speed: (5.0, 25.0),
+    direction: Vec2::Y,
+    spread: 0.8,
+    drag: 2.0,
+    color: (Color::rgba(1.0, 0.75, 0.3, 0.9), Color::rgba(0.8, 0.15, 0.05, 0.0)),
+    size: (6.0, 2.0),
+};
+
+// Enemy death
+pub const EMBER_BURST: EmitterDef = EmitterDef {
+    rate: 0.0,
+    lifetime: (0.35, 0.7),
+    speed: (60.0, 160.0),
+    direction: Vec2::Y,
+    spread: std::f32::consts::PI,
+    drag: 4.0,
+    color: (Color::rgba(1.0, 0.6, 0.2, 1.0), Color::rgba(0.4, 0.05, 0.0, 0.0)),
+    size: (5.0, 1.5),
+};
+
+// XP orb collected
+pub const XP_SPARKLE: EmitterDef = EmitterDef {
+    rate: 0.0,
+    lifetime: (0.25, 0.5),
+    speed: (30.0, 90.0),
+    direction: Vec2::Y,
+    spread: std::f32::consts::PI,
+    drag: 3.0,
+    color: (Color::rgba(0.6, 0.9, 1.0, 1.0), Color::rgba(1.0, 1.0, 1.0, 0.0)),
+    size: (4.0, 1.0),
+};
+
+// Continuous emitter following its entity's world position
+#[derive(Component)]
+pub struct Emitter {
+    def: &'static EmitterDef,
+    carry: f32,
+}
+
+impl Emitter {
+    pub fn new(def: &'static EmitterDef) -> Self {
+        Self { def, carry: 0.0 }
+    }
+}
+
+// One-shot spray of `count` particles at `pos`
+#[derive(Event)]
+pub struct ParticleBurst {
+    pub pos: Vec2,
+    pub def: &'static EmitterDef,
+    pub count: usize,
+}
+
+#[derive(Component)]
+pub struct Particle {
+    def: &'static EmitterDef,
+    age: f32,
+    lifetime: f32,
+    velocity: Vec2,
+}
+
+impl Particle {
+    pub fn new(def: &'static EmitterDef, lifetime: f32, velocity: Vec2) -> Self {
+        Self { def, age: 0.0, lifetime, velocity }
+    }
+}
+
+// How many particles may be alive right now; shrinks when the frame rate drops
+#[derive(Resource)]
+pub struct ParticleBudget {
+    pub max: usize,
+}
+
+impl Default for ParticleBudget {
+    fn default() -> Self { Self { max: MAX_PARTICLES } }
+}
+
+/// Budget for a smoothed frame rate: the full cap at `HIGH_FPS` and up, the floor at `LOW_FPS` and below.
+pub fn budget_for_fps(fps: f64) -> usize {
+    let t = ((fps - LOW_FPS) / (HIGH_FPS - LOW_FPS)).clamp(0.0, 1.0);
+    MIN_PARTICLES + ((MAX_PARTICLES - MIN_PARTICLES) as f64 * t) as usize
+}
+
+// Retired particle entities, hidden and waiting for reuse
+#[derive(Resource, Default)]
+struct ParticlePool(Vec<Entity>);
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticleBudget>()
+            .init_resource::<ParticlePool>()
+            .add_event::<ParticleBurst>()
+            .add_systems(Update, (
+                attach_flame_trails,
+                update_particle_budget,
+                emit_particles,
+                update_particles,
+            ).chain().after(CombatSet).run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn attach_flame_trails(mut commands: Commands, flames: Query<Entity, Added<OrbitingFlame>>) {
+    for entity in flames.iter() {
+        commands.entity(entity).insert(Emitter::new(&FLAME_TRAIL));
+    }
+}
+
+fn update_particle_budget(diagnostics: Res<DiagnosticsStore>, mut budget: ResMut<ParticleBudget>) {
+    let Some(fps) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS).and_then(|d| d.smoothed()) else { return; };
+    let max = budget_for_fps(fps);
+    if budget.max != max { budget.max = max; }
+}
+
+fn emit_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    budget: Res<ParticleBudget>,
+    mut pool: ResMut<ParticlePool>,
+    mut bursts: EventReader<ParticleBurst>,
+    mut emitters: Query<(&mut Emitter, &GlobalTransform, &InheritedVisibility)>,
+    live: Query<(), With<Particle>>,
+) {
+    let mut room = budget.max.saturating_sub(live.iter().count());
+    let mut rng = rand::rng();
+    let mut spawn = |def: &'static EmitterDef, pos: Vec2, count: usize| {
+        for _ in 0..count.min(room) {
+            spawn_particle(&mut commands, &mut pool, &mut rng, def, pos);
+        }
+        room = room.saturating_sub(count);
+    };
+    // Bursts first: a death or pickup matters more than one more trail ember
+    for burst in bursts.read() {
+        spawn(burst.def, burst.pos, burst.count);
+    }
+    let dt = time.delta_seconds();
+    for (mut emitter, gtf, vis) in emitters.iter_mut() {
+        if !vis.get() { continue; }
+        emitter.carry += emitter.def.rate * dt;
+        let count = emitter.carry as usize;
+        emitter.carry -= count as f32;
+        spawn(emitter.def, gtf.translation().truncate(), count);
+    }
+}
+
+fn spawn_particle(commands: &mut Commands, pool: &mut ParticlePool, rng: &mut impl Rng, def: &'static EmitterDef, pos: Vec2) {
+    let angle = def.direction.to_angle() + rng.random_range(-def.spread..=def.spread);
+    let particle = Particle::new(
+        def,
+        rng.random_range(def.lifetime.0..=def.lifetime.1),
+        Vec2::from_angle(angle) * rng.random_range(def.speed.0..=def.speed.1),
+    );
+    let sprite = Sprite { color: def.color.0, custom_size: Some(Vec2::splat(def.size.0)), ..default() };
+    let transform = Transform::from_translation(pos.extend(PARTICLE_Z));
+    match pool.0.pop() {
+        Some(entity) => { commands.entity(entity).insert((particle, sprite, transform, Visibility::Inherited)); }
+        None => { commands.spawn((particle, SpriteBundle { sprite, transform, ..default() })); }
+    }
+}
+
+fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    let (a, b) = (Vec4::from(a.as_rgba_f32()), Vec4::from(b.as_rgba_f32()));
+    let c = a.lerp(b, t);
+    Color::rgba(c.x, c.y, c.z, c.w)
+}
+
+fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pool: ResMut<ParticlePool>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, mut p, mut tf, mut sprite) in particles.iter_mut() {
+        p.age += dt;
+        if p.age >= p.lifetime {
+            commands.entity(entity).remove::<Particle>().insert(Visibility::Hidden);
+            pool.0.push(entity);
+            continue;
+        }
+        let (def, t) = (p.def, p.age / p.lifetime);
+        p.velocity *= (1.0 - def.drag * dt).max(0.0);
+        tf.translation += (p.velocity * dt).extend(0.0);
+        sprite.color = lerp_color(def.color.0, def.color.1, t);
+        sprite.custom_size = Some(Vec2::splat(def.size.0 + (def.size.1 - def.size.0) * t));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_shrinks_with_frame_rate() {
+        assert_eq!(budget_for_fps(144.0), MAX_PARTICLES);
+        assert_eq!(budget_for_fps(HIGH_FPS), MAX_PARTICLES);
+        assert_eq!(budget_for_fps(10.0), MIN_PARTICLES);
+        let mid = budget_for_fps((LOW_FPS + HIGH_FPS) / 2.0);
+        assert!(mid > MIN_PARTICLES && mid < MAX_PARTICLES);
+    }
+}
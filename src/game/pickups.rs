@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use super::camera::{visible_world_rect, CameraTrauma, MainCamera};
+use super::combat::{kill_enemy, CombatSet};
+use super::enemy::{Enemy, EnemyHealth, EnemyRank};
+use super::player::{Player, PlayerStats};
+use super::states::GameState;
+use super::xp::{Magnetized, XpOrb};
+
+pub struct PickupsPlugin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickupKind {
+    // Floor chicken: restores a chunk of HP
+    Food,
+    // Kills every enemy currently on screen
+    Bomb,
+    // Freezes all enemies for a few seconds
+    Clock,
+    // Pulls every XP orb on the map to the player
+    Magnet,
+}
+
+impl PickupKind {
+    fn look(self) -> (Color, Vec2) {
+        match self {
+            PickupKind::Food => (Color::rgb(0.95, 0.55, 0.35), Vec2::new(18.0, 14.0)),
+            PickupKind::Bomb => (Color::rgb(0.2, 0.2, 0.2), Vec2::splat(16.0)),
+            PickupKind::Clock => (Color::rgb(0.5, 0.85, 1.0), Vec2::splat(16.0)),
+            PickupKind::Magnet => (Color::rgb(0.9, 0.15, 0.25), Vec2::new(14.0, 18.0)),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Pickup(pub PickupKind);
+
+#[derive(Event)]
+pub struct PickupCollected(pub PickupKind);
+
+// While running, enemies stand still (see `enemy_seek`)
+#[derive(Resource, Default)]
+pub struct EnemyFreeze(pub Option<Timer>);
+
+impl EnemyFreeze {
+    pub fn active(&self) -> bool { self.0.is_some() }
+}
+
+// Chance that any kill drops a pickup, and the relative weight of each kind
+const PICKUP_DROP_CHANCE: f64 = 0.03;
+const PICKUP_WEIGHTS: &[(PickupKind, u32)] = &[
+    (PickupKind::Food, 50),
+    (PickupKind::Magnet, 20),
+    (PickupKind::Clock, 15),
+    (PickupKind::Bomb, 15),
+];
+const PICKUP_DISTANCE: f32 = 28.0;
+const FOOD_HEAL: f32 = 30.0;
+const FREEZE_SECONDS: f32 = 5.0;
+
+pub fn maybe_spawn_pickup_at(commands: &mut Commands, pos: Vec2) {
+    let mut rng = rand::rng();
+    if !rng.random_bool(PICKUP_DROP_CHANCE) { return; }
+    let total: u32 = PICKUP_WEIGHTS.iter().map(|(_, w)| w).sum();
+    let mut roll = rng.random_range(0..total);
+    for (kind, weight) in PICKUP_WEIGHTS {
+        if roll < *weight {
+            spawn_pickup_at(commands, pos, *kind);
+            return;
+        }
+        roll -= weight;
+    }
+}
+
+pub fn spawn_pickup_at(commands: &mut Commands, pos: Vec2, kind: PickupKind) {
+    let (color, size) = kind.look();
+    commands.spawn((
+        Pickup(kind),
+        SpriteBundle {
+            sprite: Sprite { color, custom_size: Some(size), ..default() },
+            transform: Transform::from_xyz(pos.x, pos.y, 0.5),
+            ..default()
+        },
+    ));
+}
+
+impl Plugin for PickupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyFreeze>()
+            .add_event::<PickupCollected>()
+            .add_systems(Update, (
+                collect_pickups,
+                (apply_food, apply_bomb, apply_clock, apply_magnet),
+            ).chain().before(CombatSet).run_if(in_state(GameState::Playing)))
+            .add_systems(Update, tick_enemy_freeze.run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn collect_pickups(
+    mut commands: Commands,
+    player: Query<&Transform, With<Player>>,
+    pickups: Query<(Entity, &Transform, &Pickup)>,
+    mut collected: EventWriter<PickupCollected>,
+) {
+    let Ok(player_tf) = player.get_single() else { return; };
+    let ppos = player_tf.translation.truncate();
+    for (entity, tf, pickup) in pickups.iter() {
+        if tf.translation.truncate().distance(ppos) < PICKUP_DISTANCE {
+            collected.send(PickupCollected(pickup.0));
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn apply_food(mut events: EventReader<PickupCollected>, mut stats: ResMut<PlayerStats>) {
+    for _ in events.read().filter(|e| e.0 == PickupKind::Food) {
+        stats.hp = (stats.hp + FOOD_HEAL).min(stats.max_hp);
+    }
+}
+
+fn apply_bomb(
+    mut commands: Commands,
+    mut events: EventReader<PickupCollected>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth, &EnemyRank), With<Enemy>>,
+    mut trauma: EventWriter<CameraTrauma>,
+) {
+    if events.read().filter(|e| e.0 == PickupKind::Bomb).count() == 0 { return; }
+    trauma.send(CameraTrauma(0.6));
+    let Ok((camera, cam_tf)) = camera_q.get_single() else { return; };
+    let Some(visible) = visible_world_rect(camera, cam_tf) else { return; };
+    // Same death path as combat kills, so XP, chests and pickups still drop
+    for (entity, tf, mut eh, rank) in enemies.iter_mut() {
+        let pos = tf.translation.truncate();
+        if visible.contains(pos) {
+            eh.hp = 0.0;
+            kill_enemy(&mut commands, entity, pos, *rank);
+        }
+    }
+}
+
+fn apply_clock(mut events: EventReader<PickupCollected>, mut freeze: ResMut<EnemyFreeze>) {
+    for _ in events.read().filter(|e| e.0 == PickupKind::Clock) {
+        freeze.0 = Some(Timer::from_seconds(FREEZE_SECONDS, TimerMode::Once));
+    }
+}
+
+fn apply_magnet(
+    mut commands: Commands,
+    mut events: EventReader<PickupCollected>,
+    orbs: Query<Entity, (With<XpOrb>, Without<Magnetized>)>,
+) {
+    if events.read().filter(|e| e.0 == PickupKind::Magnet).count() == 0 { return; }
+    for orb in orbs.iter() {
+        commands.entity(orb).insert(Magnetized);
+    }
+}
+
+fn tick_enemy_freeze(time: Res<Time>, mut freeze: ResMut<EnemyFreeze>) {
+    let done = match freeze.0.as_mut() {
+        Some(timer) => timer.tick(time.delta()).finished(),
+        None => return,
+    };
+    if done { freeze.0 = None; }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::camera::{camera_system, ManualTextureViews};
+    use bevy::window::{PrimaryWindow, WindowCreated, WindowResized, WindowResolution, WindowScaleFactorChanged};
+    use crate::game::audio::PlaySfx;
+    use crate::game::chest::Chest;
+    use crate::game::particles::ParticleBurst;
+
+    fn spawn_enemy(app: &mut App, pos: Vec2, rank: EnemyRank) -> Entity {
+        app.world.spawn((Enemy, EnemyHealth { hp: 100.0, max: 100.0 }, rank, Transform::from_translation(pos.extend(0.0)))).id()
+    }
+
+    #[test]
+    fn bomb_kills_on_screen_enemies_with_their_drops() {
+        let mut app = App::new();
+        // Headless cameras only get a viewport once camera_system sees a window
+        app.init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<AssetEvent<Image>>()
+            .add_event::<PickupCollected>()
+            .add_event::<CameraTrauma>()
+            .add_event::<PlaySfx>()
+            .add_event::<ParticleBurst>()
+            .add_systems(Update, (camera_system::<OrthographicProjection>, apply_bomb).chain());
+        app.world.spawn((Window { resolution: WindowResolution::new(800.0, 600.0), ..default() }, PrimaryWindow));
+        app.world.spawn((Camera2dBundle::default(), MainCamera));
+        app.update();
+
+        spawn_enemy(&mut app, Vec2::new(10.0, 0.0), EnemyRank::Normal);
+        spawn_enemy(&mut app, Vec2::new(-100.0, 80.0), EnemyRank::Elite);
+        let far = spawn_enemy(&mut app, Vec2::new(2000.0, 0.0), EnemyRank::Normal);
+        app.world.send_event(PickupCollected(PickupKind::Bomb));
+        app.update();
+
+        let mut orbs: Vec<u32> = app.world.query::<&XpOrb>().iter(&app.world).map(|o| o.value).collect();
+        orbs.sort();
+        assert_eq!(orbs, vec![EnemyRank::Normal.xp_value(), EnemyRank::Elite.xp_value()]);
+        assert_eq!(app.world.query_filtered::<(), With<Chest>>().iter(&app.world).count(), 1, "the elite drops a chest");
+        let alive: Vec<Entity> = app.world.query_filtered::<Entity, With<Enemy>>().iter(&app.world).collect();
+        assert_eq!(alive, vec![far], "only the off-screen enemy survives");
+    }
+}
@@ -2,9 +2,14 @@ use bevy::prelude::*;
 
 use super::states::GameState;
  
-use bevy::asset::AssetServer;
+use super::assets::PlayerAssets;
+use bevy::ecs::system::SystemParam;
 use bevy::window::PrimaryWindow;
-use bevy::input::touch::{TouchInput, TouchPhase};
+
+use super::animation::{Animator, Clip, ClipId, Looping, SheetDef};
+use super::camera::MainCamera;
+use super::input::{ActionState, ControlScheme};
+use super::touch::VirtualStick;
 
 pub struct PlayerPlugin;
 
@@ -13,7 +18,7 @@ pub struct Player;
 
 #[derive(Component)]
 pub struct PlayerAura; // visual + radius indicator child
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct OrbitingFlame {
     pub angle: f32,
     pub radius: f32,
@@ -25,6 +30,51 @@ pub struct OrbitingFlame {
 #[derive(Component, Deref, DerefMut)]
 pub struct MoveSpeed(pub f32);
 
+// The player's animated body sprite (a child of `Player`)
+#[derive(Component)]
+struct PlayerBody;
+
+// mainchar.png and flame.png are single frames for now; new art only needs the grid and clip ranges updated
+pub const PLAYER_SHEET: SheetDef = SheetDef {
+    tile: UVec2::new(1024, 1024),
+    columns: 1,
+    rows: 1,
+    clips: &[
+        Clip { id: ClipId::Idle, first: 0, frames: 1, fps: 6.0, looping: Looping::Loop },
+        Clip { id: ClipId::Walk, first: 0, frames: 1, fps: 10.0, looping: Looping::Loop },
+        Clip { id: ClipId::Hurt, first: 0, frames: 1, fps: 12.0, looping: Looping::Once },
+        Clip { id: ClipId::Death, first: 0, frames: 1, fps: 8.0, looping: Looping::Once },
+    ],
+};
+
+pub const FLAME_SHEET: SheetDef = SheetDef {
+    tile: UVec2::new(1024, 1536),
+    columns: 1,
+    rows: 1,
+    clips: &[Clip { id: ClipId::Flicker, first: 0, frames: 1, fps: 12.0, looping: Looping::PingPong }],
+};
+
+// Last direction the player moved in (unit length); flips the body sprite and orients melee / breath weapons
+#[derive(Component, Deref, Clone, Copy)]
+pub struct Facing(pub Vec2);
+
+impl Default for Facing {
+    fn default() -> Self { Self(Vec2::X) }
+}
+
+// Unit direction directional weapons fire along (see `update_player_aim`)
+#[derive(Resource, Deref)]
+pub struct PlayerAim(pub Vec2);
+
+impl Default for PlayerAim {
+    fn default() -> Self { Self(Vec2::X) }
+}
+
+// Small marker shown around the player in the twin-stick scheme
+#[derive(Component)]
+struct AimReticle;
+const AIM_RETICLE_DISTANCE: f32 = 44.0;
+
 #[derive(Resource, Default)]
 pub struct PlayerStats {
     pub xp: u32,
@@ -42,22 +92,23 @@ impl PlayerStats {
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PlayerStats>()
-            .init_resource::<TouchState>()
+            .init_resource::<PlayerAim>()
             .add_systems(OnEnter(GameState::Playing), spawn_player)
             .add_systems(Update, (
-                touch_capture_system,
                 player_movement,
+                update_player_aim.after(player_movement),
+                flip_player_sprite.after(player_movement),
                 animate_orbiting_flames,
                 sync_flame_radius,
-                reveal_flames_when_ready,
-            ).run_if(in_state(GameState::Playing)));
+            ).run_if(in_state(GameState::Playing)))
+            .add_systems(Update, drive_player_clips.run_if(in_state(GameState::Playing).or_else(in_state(GameState::GameOver))));
     }
 }
 fn spawn_player(
     mut commands: Commands,
     mut stats: ResMut<PlayerStats>,
     existing: Query<Entity, With<Player>>, 
-    asset_server: Res<AssetServer>,
+    player_assets: Res<PlayerAssets>,
 ) {
     if existing.iter().next().is_some() {
         // Already have a player (likely returning from Pause) -> do not respawn or reset stats
@@ -72,98 +123,126 @@ fn spawn_player(
     let mut player = commands.spawn((
         Player,
         MoveSpeed(350.0),
+        Facing::default(),
         SpatialBundle::default(),
     ));
     player.with_children(|parent| {
-        // Player sprite: start white while texture loads; use a modest size like 48x48
-        parent.spawn(SpriteBundle {
-            texture: asset_server.load("sprites/mainchar.png"),
+        // Player sprite at a modest 48x48
+        parent.spawn((PlayerBody, Animator::new(&PLAYER_SHEET, ClipId::Idle), SpriteSheetBundle {
+            texture: player_assets.mainchar.clone(),
+            atlas: TextureAtlas { layout: player_assets.mainchar_layout.clone(), index: 0 },
             sprite: Sprite { color: Color::WHITE, custom_size: Some(Vec2::splat(48.0)), ..default() },
             transform: Transform::from_xyz(0.0, 0.0, 1.0),
             ..default()
-        });
+        }));
+        parent.spawn((AimReticle, SpriteBundle {
+            sprite: Sprite { color: Color::rgba(1.0, 0.9, 0.5, 0.8), custom_size: Some(Vec2::new(12.0, 4.0)), ..default() },
+            visibility: Visibility::Hidden,
+            ..default()
+        }));
         // Orbiting flames replacing the aura quad
         let flame_count = 6;
         let radius = 120.0; // match combat aura radius for visual consistency
         for i in 0..flame_count {
             let angle = i as f32 / flame_count as f32 * std::f32::consts::TAU;
-            parent.spawn((PlayerAura, OrbitingFlame { angle, radius, speed: 1.8, contact_radius: 16.0, contact_damage: 5.0 }, SpriteBundle {
-                texture: asset_server.load("sprites/flame.png"),
-                // Start fully transparent to avoid untextured colored quads before texture finishes loading
-                sprite: Sprite { color: Color::rgba(1.0, 1.0, 1.0, 0.0), custom_size: Some(Vec2::splat(32.0)), ..default() },
+            parent.spawn((PlayerAura, OrbitingFlame { angle, radius, speed: 1.8, contact_radius: 16.0, contact_damage: 5.0 }, Animator::new(&FLAME_SHEET, ClipId::Flicker), SpriteSheetBundle {
+                texture: player_assets.flame.clone(),
+                atlas: TextureAtlas { layout: player_assets.flame_layout.clone(), index: 0 },
+                sprite: Sprite { custom_size: Some(Vec2::splat(32.0)), ..default() },
                 transform: Transform::from_xyz(angle.cos() * radius, angle.sin() * radius, 0.0),
                 ..default()
             }));
         }
     });
-    commands.spawn(Camera2dBundle::default());
 }
 
 fn player_movement(
-    kb: Res<ButtonInput<KeyCode>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
-    camera_q: Query<(&Camera, &GlobalTransform)>,
-    mut q: Query<(&MoveSpeed, &mut Transform), With<Player>>,
+    actions: Res<ActionState>,
+    scheme: Res<ControlScheme>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    pointer: PointerWorld,
+    mut q: Query<(&MoveSpeed, &mut Transform, &mut Facing), With<Player>>,
     time: Res<Time>,
-    touch: Res<TouchState>,
+    virtual_stick: Res<VirtualStick>,
 ) {
-    if let Some((speed, mut tf)) = q.iter_mut().next() {
-        // 1) Pointer follow: prefer active touch if present, else mouse cursor
-        let mut moved_by_pointer = false;
-        if let Ok(window) = windows.get_single() {
-            let screen_pos = if touch.active { touch.position } else { window.cursor_position() };
-            if let Some(cursor_pos) = screen_pos {
-                if let Ok((camera, cam_tf)) = camera_q.get_single() {
-                    if let Some(world_pos) = camera.viewport_to_world_2d(cam_tf, cursor_pos) {
-                        let player_pos = tf.translation.truncate();
-                        let to_target = world_pos - player_pos;
-                        if to_target.length_squared() > 1.0 { // small deadzone
-                            let dir = to_target.normalize();
-                            tf.translation.x += dir.x * **speed * time.delta_seconds();
-                            tf.translation.y += dir.y * **speed * time.delta_seconds();
-                            moved_by_pointer = true;
-                        }
-                    }
-                }
+    let Some((speed, mut tf, mut facing)) = q.iter_mut().next() else { return; };
+    // 1) Sticks (pad / on-screen; analog: partial tilt = partial speed) and move keys always win
+    let mut dir = actions.move_axis;
+    // 2) Otherwise the pointer may steer, depending on the scheme. Off once touch is used,
+    //    since browsers emulate the cursor from touches.
+    let pointer_steers = match *scheme {
+        ControlScheme::PointerFollow => true,
+        ControlScheme::HoldToMove => mouse.pressed(MouseButton::Left),
+        ControlScheme::Keyboard | ControlScheme::TwinStick => false,
+    };
+    if dir == Vec2::ZERO && pointer_steers && !virtual_stick.touch_seen {
+        if let Some(world_pos) = pointer.cursor() {
+            let to_target = world_pos - tf.translation.truncate();
+            if to_target.length_squared() > 1.0 { // small deadzone
+                dir = to_target.normalize();
             }
         }
+    }
+    tf.translation.x += dir.x * **speed * time.delta_seconds();
+    tf.translation.y += dir.y * **speed * time.delta_seconds();
+    if let Some(unit) = dir.try_normalize() {
+        if facing.0 != unit { facing.0 = unit; }
+    }
+}
 
-        // 2) Fallback to keyboard if pointer didn't move us
-        if !moved_by_pointer {
-            let mut dir = Vec2::ZERO;
-            if kb.pressed(KeyCode::KeyW) { dir.y += 1.0; }
-            if kb.pressed(KeyCode::KeyS) { dir.y -= 1.0; }
-            if kb.pressed(KeyCode::KeyA) { dir.x -= 1.0; }
-            if kb.pressed(KeyCode::KeyD) { dir.x += 1.0; }
-            if dir.length_squared() > 0.0 { dir = dir.normalize(); }
-            tf.translation.x += dir.x * **speed * time.delta_seconds();
-            tf.translation.y += dir.y * **speed * time.delta_seconds();
-        }
+// mainchar.png faces right; mostly-vertical movement keeps whichever side it last faced
+fn flip_player_sprite(
+    player: Query<&Facing, (With<Player>, Changed<Facing>)>,
+    mut body: Query<&mut Sprite, With<PlayerBody>>,
+) {
+    let Ok(facing) = player.get_single() else { return; };
+    if facing.x.abs() < 0.2 { return; }
+    for mut sprite in body.iter_mut() {
+        sprite.flip_x = facing.x < 0.0;
     }
 }
 
-// Track current touch state (screen-space position and active flag)
-#[derive(Resource, Default)]
-struct TouchState {
-    position: Option<Vec2>,
-    active: bool,
+// Mouse cursor resolved into world space
+#[derive(SystemParam)]
+struct PointerWorld<'w, 's> {
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<MainCamera>>,
+}
+
+impl PointerWorld<'_, '_> {
+    // Camera follows the player, so always resolve the pointer through its current transform
+    fn cursor(&self) -> Option<Vec2> {
+        let cursor = self.windows.get_single().ok()?.cursor_position()?;
+        let (camera, cam_tf) = self.camera.get_single().ok()?;
+        camera.viewport_to_world_2d(cam_tf, cursor)
+    }
 }
 
-fn touch_capture_system(
-    mut ev_touch: EventReader<TouchInput>,
-    mut state: ResMut<TouchState>,
+// The reticle sprite, a child of the player
+type ReticleQuery<'w, 's> = Query<'w, 's, (&'static mut Transform, &'static mut Visibility), (With<AimReticle>, Without<Player>)>;
+
+// Twin-stick aims at the cursor; other schemes aim where the player faces
+fn update_player_aim(
+    scheme: Res<ControlScheme>,
+    pointer: PointerWorld,
+    player: Query<(&Transform, &Facing), With<Player>>,
+    mut aim: ResMut<PlayerAim>,
+    mut reticle: ReticleQuery,
 ) {
-    for ev in ev_touch.read() {
-        match ev.phase {
-            TouchPhase::Started | TouchPhase::Moved => {
-                state.position = Some(ev.position);
-                state.active = true;
-            }
-            TouchPhase::Ended | TouchPhase::Canceled => {
-                state.position = None;
-                state.active = false;
-            }
-        }
+    let Ok((player_tf, facing)) = player.get_single() else { return; };
+    let twin_stick = *scheme == ControlScheme::TwinStick;
+    let target = if twin_stick {
+        pointer.cursor().map(|p| p - player_tf.translation.truncate())
+    } else {
+        Some(facing.0)
+    };
+    if let Some(dir) = target.and_then(|d| d.try_normalize()) {
+        aim.0 = dir;
+    }
+    for (mut tf, mut vis) in reticle.iter_mut() {
+        tf.translation = (aim.0 * AIM_RETICLE_DISTANCE).extend(2.0);
+        tf.rotation = Quat::from_rotation_z(aim.0.to_angle());
+        vis.set_if_neq(if twin_stick { Visibility::Inherited } else { Visibility::Hidden });
     }
 }
 
@@ -193,17 +272,24 @@ fn sync_flame_radius(cfg: Res<AuraConfig>, mut flames: Query<&mut OrbitingFlame,
     }
 }
 
-fn reveal_flames_when_ready(
-    images: Res<Assets<Image>>,
-    mut q: Query<(&Handle<Image>, &mut Sprite), With<PlayerAura>>,
+// Death on the GameOver screen, Hurt whenever HP drops, otherwise Walk / Idle from how far the player moved
+fn drive_player_clips(
+    state: Res<State<GameState>>,
+    stats: Res<PlayerStats>,
+    player: Query<&Transform, With<Player>>,
+    mut body: Query<&mut Animator, With<PlayerBody>>,
+    mut last: Local<Option<(Vec2, f32)>>,
 ) {
-    // Once any flame texture is loaded, ensure it becomes visible (white tint, full alpha)
-    for (handle, mut sprite) in q.iter_mut() {
-        if images.get(handle).is_some() {
-            sprite.color = Color::rgba(1.0, 1.0, 1.0, 1.0);
-        }
+    let Ok(tf) = player.get_single() else { return; };
+    let pos = tf.translation.truncate();
+    let (last_pos, last_hp) = last.unwrap_or((pos, stats.hp));
+    *last = Some((pos, stats.hp));
+    let Ok(mut anim) = body.get_single_mut() else { return; };
+    if *state.get() == GameState::GameOver {
+        anim.play(ClipId::Death);
+    } else if stats.hp < last_hp {
+        anim.play(ClipId::Hurt);
+    } else if anim.clip() != ClipId::Hurt || anim.finished() {
+        anim.play(if pos.distance_squared(last_pos) > 0.01 { ClipId::Walk } else { ClipId::Idle });
     }
 }
-
-
-// (Removed texture upgrade system; not needed as we tint white by default and handle loads asynchronously.)
@@ -0,0 +1,271 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use super::audio::AudioVolumes;
+use super::camera::CameraRig;
+use super::i18n::LOCALES;
+use super::input::{key_from_name, key_name, Action, ControlScheme, InputMap};
+
+pub struct SettingsPlugin;
+
+// Bump when fields are added or change meaning. Older files simply lack the new keys and keep
+// their defaults; keys this build doesn't know (newer files) are carried through on save.
+// v1: key bindings, control scheme. v2: audio volumes, display options, language.
+pub const SETTINGS_VERSION: u32 = 2;
+
+// Request a write of the current settings (sent after any change in the UI)
+#[derive(Event, Default)]
+pub struct SaveSettings;
+
+// Display & gameplay preferences that don't belong to another module's resource
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Preferences {
+    pub show_fps: bool,
+    // Multiplier on camera shake, 0..=1
+    pub screen_shake: f32,
+    pub damage_numbers: bool,
+    pub language: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self { show_fps: true, screen_shake: 1.0, damage_numbers: true, language: "en".into() }
+    }
+}
+
+// Everything that is persisted, gathered from the resources that own each part
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub keys: HashMap<Action, Vec<KeyCode>>,
+    pub scheme: ControlScheme,
+    pub volumes: AudioVolumes,
+    pub prefs: Preferences,
+    // `key=value` lines this build doesn't understand, written back untouched
+    pub unknown: Vec<(String, String)>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            keys: InputMap::default().keys,
+            scheme: ControlScheme::default(),
+            volumes: AudioVolumes::default(),
+            prefs: Preferences::default(),
+            unknown: Vec::new(),
+        }
+    }
+}
+
+// Unknown lines from the loaded file, kept so saving doesn't drop them
+#[derive(Resource, Default)]
+struct UnknownSettings(Vec<(String, String)>);
+
+// The live resources settings are read from and written to
+#[derive(SystemParam)]
+pub struct SettingsResources<'w> {
+    map: ResMut<'w, InputMap>,
+    scheme: ResMut<'w, ControlScheme>,
+    volumes: ResMut<'w, AudioVolumes>,
+    prefs: ResMut<'w, Preferences>,
+    unknown: ResMut<'w, UnknownSettings>,
+}
+
+impl SettingsResources<'_> {
+    pub fn snapshot(&self) -> Settings {
+        Settings {
+            keys: self.map.keys.clone(),
+            scheme: *self.scheme,
+            volumes: *self.volumes,
+            prefs: self.prefs.clone(),
+            unknown: self.unknown.0.clone(),
+        }
+    }
+
+    pub fn store(&mut self, settings: Settings) {
+        self.map.keys = settings.keys;
+        *self.scheme = settings.scheme;
+        *self.volumes = settings.volumes;
+        *self.prefs = settings.prefs;
+        self.unknown.0 = settings.unknown;
+    }
+}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveSettings>()
+            .init_resource::<Preferences>()
+            .init_resource::<UnknownSettings>()
+            .add_systems(Startup, load_settings)
+            .add_systems(Update, apply_preferences)
+            .add_systems(Last, save_settings);
+    }
+}
+
+fn load_settings(mut res: SettingsResources) {
+    let Some(text) = storage::read() else { return; };
+    let mut settings = res.snapshot();
+    let version = settings.apply_text(&text);
+    if version > SETTINGS_VERSION {
+        warn!("Settings file is version {version} (this build writes {SETTINGS_VERSION}); unknown fields are kept");
+    }
+    res.store(settings);
+}
+
+fn save_settings(mut requests: EventReader<SaveSettings>, res: SettingsResources) {
+    if requests.is_empty() { return; }
+    requests.clear();
+    if let Err(err) = storage::write(&res.snapshot().to_text()) {
+        warn!("Could not save settings: {err}");
+    }
+}
+
+// Push preferences owned here into the systems they tune
+fn apply_preferences(prefs: Res<Preferences>, mut rig: ResMut<CameraRig>) {
+    if !prefs.is_changed() { return; }
+    rig.shake_scale = prefs.screen_shake.clamp(0.0, 1.0);
+}
+
+impl Settings {
+    // Plain `key=value` lines so the file stays hand-editable
+    pub fn to_text(&self) -> String {
+        let mut out = format!("version={SETTINGS_VERSION}\n");
+        out.push_str(&format!("control_scheme={}\n", self.scheme.id()));
+        for action in Action::ALL {
+            let keys: Vec<String> = self.keys.get(&action).into_iter().flatten().map(|k| key_name(*k)).collect();
+            out.push_str(&format!("bind.{}={}\n", action.id(), keys.join(" ")));
+        }
+        out.push_str(&format!("volume.master={}\n", self.volumes.master));
+        out.push_str(&format!("volume.music={}\n", self.volumes.music));
+        out.push_str(&format!("volume.sfx={}\n", self.volumes.sfx));
+        out.push_str(&format!("display.show_fps={}\n", self.prefs.show_fps));
+        out.push_str(&format!("display.screen_shake={}\n", self.prefs.screen_shake));
+        out.push_str(&format!("display.damage_numbers={}\n", self.prefs.damage_numbers));
+        out.push_str(&format!("language={}\n", self.prefs.language));
+        for (key, value) in &self.unknown {
+            out.push_str(&format!("{key}={value}\n"));
+        }
+        out
+    }
+
+    /// Overlay a settings file onto `self` and return the file's version (1 when unversioned).
+    /// Malformed values are skipped so the field keeps its current value.
+    pub fn apply_text(&mut self, text: &str) -> u32 {
+        let mut version = 1;
+        let unit = |v: &str| v.parse::<f32>().ok().filter(|x| (0.0..=1.0).contains(x));
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else { continue; };
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "version" => version = value.parse().unwrap_or(version),
+                "control_scheme" => if let Some(s) = ControlScheme::from_id(value) { self.scheme = s; },
+                "volume.master" => if let Some(v) = unit(value) { self.volumes.master = v; },
+                "volume.music" => if let Some(v) = unit(value) { self.volumes.music = v; },
+                "volume.sfx" => if let Some(v) = unit(value) { self.volumes.sfx = v; },
+                "display.show_fps" => if let Ok(b) = value.parse() { self.prefs.show_fps = b; },
+                "display.screen_shake" => if let Some(v) = unit(value) { self.prefs.screen_shake = v; },
+                "display.damage_numbers" => if let Ok(b) = value.parse() { self.prefs.damage_numbers = b; },
+                "language" => if LOCALES.iter().any(|(id, _)| *id == value) { self.prefs.language = value.into(); },
+                _ => match key.strip_prefix("bind.").map(Action::from_id) {
+                    Some(Some(action)) => {
+                        let keys: Vec<KeyCode> = value.split_whitespace().filter_map(key_from_name).collect();
+                        if !keys.is_empty() { self.keys.insert(action, keys); }
+                    }
+                    // A binding for an action this build lacks, or a field from a newer version
+                    _ => {
+                        self.unknown.retain(|(k, _)| k != key);
+                        self.unknown.push((key.into(), value.into()));
+                    }
+                },
+            }
+        }
+        version
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod storage {
+    use std::path::PathBuf;
+
+    fn path() -> PathBuf {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")));
+        match base {
+            Some(dir) => dir.join("slop_survivors").join("settings.cfg"),
+            None => PathBuf::from("settings.cfg"),
+        }
+    }
+
+    pub fn read() -> Option<String> {
+        std::fs::read_to_string(path()).ok()
+    }
+
+    pub fn write(text: &str) -> Result<(), String> {
+        let path = path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&path, text).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod storage {
+    const KEY: &str = "slop_survivors.settings";
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    pub fn read() -> Option<String> {
+        local_storage()?.get_item(KEY).ok().flatten()
+    }
+
+    pub fn write(text: &str) -> Result<(), String> {
+        let storage = local_storage().ok_or("localStorage unavailable")?;
+        storage.set_item(KEY, text).map_err(|e| format!("{e:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinds_survive_round_trip() {
+        // AZERTY layout: ZQSD instead of WASD
+        let mut settings = Settings::default();
+        settings.keys.insert(Action::MoveUp, vec![KeyCode::KeyZ]);
+        settings.keys.insert(Action::MoveLeft, vec![KeyCode::KeyQ, KeyCode::ArrowLeft]);
+        settings.scheme = ControlScheme::TwinStick;
+        settings.volumes.music = 0.25;
+        settings.prefs.show_fps = false;
+        let mut loaded = Settings::default();
+        assert_eq!(loaded.apply_text(&settings.to_text()), SETTINGS_VERSION);
+        assert_eq!(loaded, settings);
+    }
+
+    #[test]
+    fn v1_file_migrates_with_defaults_for_new_fields() {
+        let mut loaded = Settings::default();
+        let version = loaded.apply_text("version=1\ncontrol_scheme=hold_to_move\nbind.pause=KeyP\n");
+        assert_eq!(version, 1);
+        assert_eq!(loaded.scheme, ControlScheme::HoldToMove);
+        assert_eq!(loaded.keys[&Action::Pause], vec![KeyCode::KeyP]);
+        assert_eq!(loaded.volumes, AudioVolumes::default());
+        assert_eq!(loaded.prefs, Preferences::default());
+    }
+
+    #[test]
+    fn unknown_and_malformed_lines_are_safe() {
+        let mut loaded = Settings::default();
+        loaded.apply_text("version=9\nbind.jump=Space\nbind.pause=NotAKey\nvolume.sfx=7\ncontrol_scheme=gyro\ngarbage\n");
+        assert_eq!(loaded.keys[&Action::Pause], Settings::default().keys[&Action::Pause]);
+        assert_eq!(loaded.volumes, AudioVolumes::default());
+        assert_eq!(loaded.scheme, ControlScheme::default());
+        // Fields from a newer version survive a save by this build
+        assert!(loaded.to_text().contains("bind.jump=Space\n"));
+    }
+}
@@ -1,24 +1,67 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use rand::Rng;
 
-use super::enemy::{Enemy, EnemySpeed, EnemyHealth, EnemyHpBarFill, EnemyHpBarRoot};
+use super::animation::{Animator, ClipId};
+use super::enemy::{Enemy, EnemySpeed, EnemyHealth, EnemyHpBarFill, EnemyHpBarRoot, EnemyRank, ENEMY_SHEET};
 use super::assets::EnemyAssets;
 use super::states::GameState;
+use super::camera::{visible_world_rect, MainCamera};
+use super::player::Player;
+use super::map::MapObstacles;
+use super::status::StatusEffects;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
 
 pub struct SpawnPlugin;
 
+// How far outside the visible rect enemies appear
+const SPAWN_MARGIN: f32 = 50.0;
+// Chance to spawn on the side the player is moving toward
+const SPAWN_AHEAD_BIAS: f64 = 0.5;
+
+// Enemies further than this outside the view get moved back onto the spawn ring ahead of the player
+const RECYCLE_DISTANCE: f32 = 700.0;
+const RECYCLE_CHECK_SECONDS: f32 = 0.5;
+
+// Dead enemy kept hidden (with its HP-bar children) for reuse by the next spawn instead of despawning
 #[derive(Component)]
-struct AwaitingTexture; // marker for enemies spawned with a placeholder
+pub struct Pooled;
+
+// Dead enemy playing its death clip; no longer an `Enemy`, pooled once the clip finishes
+#[derive(Component)]
+pub struct Dying;
+
+/// Death path counterpart of `spawn_enemy`: strip the enemy state, play the death clip, then park the entity in the pool.
+pub fn release_enemy(commands: &mut Commands, entity: Entity) {
+    commands.entity(entity)
+        .remove::<(Enemy, StatusEffects)>()
+        .insert(Dying);
+}
 
 #[derive(Resource)]
-struct EnemySpawnTimer {
+pub struct EnemySpawnTimer {
     timer: Timer,
     elapsed: f32,
+    // Elites and bosses arrive on their own fixed cadence
+    elite_timer: Timer,
+    boss_timer: Timer,
+    // Used to estimate which way the player is heading
+    last_player_pos: Option<Vec2>,
+    // Player movement over the last frame, shared with the recycler
+    heading: Vec2,
 }
 
 impl Default for EnemySpawnTimer {
     fn default() -> Self {
-        Self { timer: Timer::from_seconds(1.2, TimerMode::Repeating), elapsed: 0.0 }
+        Self {
+            timer: Timer::from_seconds(1.2, TimerMode::Repeating),
+            elapsed: 0.0,
+            elite_timer: Timer::from_seconds(45.0, TimerMode::Repeating),
+            boss_timer: Timer::from_seconds(180.0, TimerMode::Repeating),
+            last_player_pos: None,
+            heading: Vec2::ZERO,
+        }
     }
 }
 
@@ -27,95 +70,155 @@ impl Plugin for SpawnPlugin {
         app.insert_resource(EnemySpawnTimer::default())
             .add_systems(Update, (
                 spawn_enemies,
-                upgrade_enemy_textures,
+                start_death_clips,
+                pool_finished_corpses,
+                recycle_distant_enemies.run_if(on_timer(Duration::from_secs_f32(RECYCLE_CHECK_SECONDS))),
             ).run_if(in_state(GameState::Playing)));
     }
 }
 
+// Where spawns may land (just outside the view, clear of obstacles) and the corpses they can reuse
+#[derive(SystemParam)]
+struct SpawnArea<'w, 's> {
+    camera_q: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<MainCamera>>,
+    map: Res<'w, MapObstacles>,
+    pooled: Query<'w, 's, Entity, With<Pooled>>,
+}
+
 fn spawn_enemies(
     mut commands: Commands,
     time: Res<Time>,
     mut timer_res: ResMut<EnemySpawnTimer>,
-    windows: Query<&Window>,
+    player_q: Query<&Transform, With<Player>>,
     enemy_assets: Res<EnemyAssets>,
-    assets_images: Res<Assets<Image>>,
+    area: SpawnArea,
 ) {
-    let Ok(primary) = windows.get_single() else { return; };
+    let Ok((camera, cam_tf)) = area.camera_q.get_single() else { return; };
+    let Some(visible) = visible_world_rect(camera, cam_tf) else { return; };
+    let heading = match player_q.get_single() {
+        Ok(player_tf) => {
+            let pos = player_tf.translation.truncate();
+            let delta = timer_res.last_player_pos.map_or(Vec2::ZERO, |last| pos - last);
+            timer_res.last_player_pos = Some(pos);
+            timer_res.heading = delta;
+            delta
+        }
+        Err(_) => Vec2::ZERO,
+    };
     // Increase difficulty over time: reduce interval gradually to a floor
     timer_res.elapsed += time.delta_seconds();
     let target = (1.2 - (timer_res.elapsed / 60.0)).clamp(0.25, 1.2); // after a minute approach 0.25s
     if (timer_res.timer.duration().as_secs_f32() - target).abs() > 0.05 {
         timer_res.timer.set_duration(std::time::Duration::from_secs_f32(target));
     }
-    if timer_res.timer.tick(time.delta()).just_finished() {
-    let mut rng = rand::rng();
-    let side = rng.random_range(0..4);
-        let w = primary.width();
-        let h = primary.height();
-        let (x, y) = match side {
-            0 => (rng.random_range(-w..w), h + 50.0),
-            1 => (rng.random_range(-w..w), -h - 50.0),
-            2 => (w + 50.0, rng.random_range(-h..h)),
-            _ => (-w - 50.0, rng.random_range(-h..h)),
-        };
-    let max_hp = rng.random_range(80.0..120.0);
-        let use_texture = assets_images.get(&enemy_assets.bucket).is_some();
-        let sprite_bundle = if use_texture {
-            SpriteBundle {
-                texture: enemy_assets.bucket.clone(),
-                sprite: Sprite { color: Color::WHITE, custom_size: Some(Vec2::splat(32.0)), ..default() },
-                transform: Transform::from_xyz(x, y, 5.0),
-                ..default()
-            }
-        } else {
-            // Visible placeholder (red quad) while texture is still loading on web
-            SpriteBundle {
-                sprite: Sprite { color: Color::rgb(0.9, 0.2, 0.2), custom_size: Some(Vec2::splat(32.0)), ..default() },
-                transform: Transform::from_xyz(x, y, 5.0),
-                ..default()
-            }
-        };
-        let mut e = commands.spawn((
-            Enemy,
-            EnemySpeed(rng.random_range(60.0..120.0)),
-            EnemyHealth { hp: max_hp, max: max_hp },
-            sprite_bundle,
-        ));
-        if !use_texture {
-            e.insert(AwaitingTexture);
+    let mut ranks = Vec::new();
+    if timer_res.timer.tick(time.delta()).just_finished() { ranks.push(EnemyRank::Normal); }
+    if timer_res.elite_timer.tick(time.delta()).just_finished() { ranks.push(EnemyRank::Elite); }
+    if timer_res.boss_timer.tick(time.delta()).just_finished() { ranks.push(EnemyRank::Boss); }
+    let mut pool = area.pooled.iter();
+    for rank in ranks {
+        let ring = Rect::from_corners(visible.min - Vec2::splat(SPAWN_MARGIN), visible.max + Vec2::splat(SPAWN_MARGIN));
+        // Never spawn inside an obstacle (or outside a bounded arena)
+        let Some(pos) = area.map.free_spot_near(pick_spawn_point(ring, heading), rank.size() * 0.5) else { continue; };
+        spawn_enemy(&mut commands, pool.next(), pos, rank, &enemy_assets);
+    }
+}
+
+fn start_death_clips(
+    mut dying: Query<(&mut Animator, &Children), Added<Dying>>,
+    mut bars: Query<&mut Visibility, With<EnemyHpBarRoot>>,
+) {
+    for (mut anim, children) in dying.iter_mut() {
+        anim.play(ClipId::Death);
+        for &child in children.iter() {
+            if let Ok(mut vis) = bars.get_mut(child) { *vis = Visibility::Hidden; }
+        }
+    }
+}
+
+fn pool_finished_corpses(mut commands: Commands, dying: Query<(Entity, &Animator), With<Dying>>) {
+    for (entity, anim) in dying.iter() {
+        if anim.finished() {
+            commands.entity(entity).remove::<Dying>().insert((Pooled, Visibility::Hidden));
         }
-        e.with_children(|parent| {
-            parent.spawn((EnemyHpBarRoot, SpriteBundle {
-                sprite: Sprite { color: Color::rgb(0.15, 0.15, 0.15), custom_size: Some(Vec2::new(24.0, 4.0)), ..default() },
-                transform: Transform::from_xyz(0.0, 20.0, 2.0),
+    }
+}
+
+// Random point on the edge of `ring` (the view plus margin), biased toward where the player is heading
+fn pick_spawn_point(ring: Rect, heading: Vec2) -> Vec2 {
+    let mut rng = rand::rng();
+    // 0 = top, 1 = bottom, 2 = right, 3 = left
+    let ahead = if heading.length_squared() < 1e-4 {
+        None
+    } else if heading.x.abs() > heading.y.abs() {
+        Some(if heading.x > 0.0 { 2 } else { 3 })
+    } else {
+        Some(if heading.y > 0.0 { 0 } else { 1 })
+    };
+    let side = match ahead {
+        Some(side) if rng.random_bool(SPAWN_AHEAD_BIAS) => side,
+        _ => rng.random_range(0..4),
+    };
+    match side {
+        0 => Vec2::new(rng.random_range(ring.min.x..=ring.max.x), ring.max.y),
+        1 => Vec2::new(rng.random_range(ring.min.x..=ring.max.x), ring.min.y),
+        2 => Vec2::new(ring.max.x, rng.random_range(ring.min.y..=ring.max.y)),
+        _ => Vec2::new(ring.min.x, rng.random_range(ring.min.y..=ring.max.y)),
+    }
+}
+
+// Spawns a fresh enemy, or revives `reuse` (a pooled entity that still has its HP-bar children)
+fn spawn_enemy(commands: &mut Commands, reuse: Option<Entity>, pos: Vec2, rank: EnemyRank, assets: &EnemyAssets) {
+    let mut rng = rand::rng();
+    let max_hp = rng.random_range(80.0..120.0) * rank.hp_multiplier();
+    let size = Vec2::splat(rank.size());
+    let sprite = Sprite { color: rank.tint(), custom_size: Some(size), ..default() };
+    let transform = Transform::from_xyz(pos.x, pos.y, 5.0);
+    // Bigger foes walk a bit slower
+    let speed = rng.random_range(60.0..120.0) * if rank == EnemyRank::Boss { 0.6 } else { 1.0 };
+    let enemy = (Enemy, rank, EnemySpeed(speed), EnemyHealth { hp: max_hp, max: max_hp }, StatusEffects::default(), Animator::new(&ENEMY_SHEET, ClipId::Walk));
+    let texture = assets.bucket.clone();
+    let atlas = TextureAtlas { layout: assets.bucket_layout.clone(), index: 0 };
+
+    if let Some(entity) = reuse {
+        commands.entity(entity).remove::<Pooled>().insert((enemy, sprite, transform, texture, atlas, Visibility::Inherited));
+        return;
+    }
+
+    let sprite_bundle = SpriteSheetBundle { texture, atlas, sprite, transform, ..default() };
+    let bar_y = size.y * 0.5 + 4.0;
+    commands.spawn((enemy, sprite_bundle)).with_children(|parent| {
+        parent.spawn((EnemyHpBarRoot, SpriteBundle {
+            sprite: Sprite { color: Color::rgb(0.15, 0.15, 0.15), custom_size: Some(Vec2::new(24.0, 4.0)), ..default() },
+            transform: Transform::from_xyz(0.0, bar_y, 2.0),
+            ..default()
+        })).with_children(|root| {
+            root.spawn((EnemyHpBarFill, SpriteBundle {
+                sprite: Sprite { color: Color::rgb(0.1, 0.9, 0.1), custom_size: Some(Vec2::new(24.0, 4.0)), ..default() },
+                // Start centered (we'll shift negatively as it shrinks to keep left edge anchored)
+                transform: Transform { translation: Vec3::new(0.0, 0.0, 0.1), scale: Vec3::new(1.0, 1.0, 1.0), ..default() },
                 ..default()
-            })).with_children(|root| {
-                root.spawn((EnemyHpBarFill, SpriteBundle {
-                    sprite: Sprite { color: Color::rgb(0.1, 0.9, 0.1), custom_size: Some(Vec2::new(24.0, 4.0)), ..default() },
-                    // Start centered (we'll shift negatively as it shrinks to keep left edge anchored)
-                    transform: Transform { translation: Vec3::new(0.0, 0.0, 0.1), scale: Vec3::new(1.0, 1.0, 1.0), ..default() },
-                    ..default()
-                }));
-            });
+            }));
         });
-    }
+    });
 }
 
-fn upgrade_enemy_textures(
-    mut commands: Commands,
-    enemy_assets: Res<EnemyAssets>,
-    assets_images: Res<Assets<Image>>,
-    mut awaiting: Query<(Entity, Option<&mut Sprite>), (With<Enemy>, With<AwaitingTexture>)>,
+// Stragglers left far behind get teleported onto the spawn ring, preferring the side the player heads to
+fn recycle_distant_enemies(
+    timer_res: Res<EnemySpawnTimer>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    map: Res<MapObstacles>,
+    mut enemies: Query<(&EnemyRank, &mut Transform), With<Enemy>>,
 ) {
-    // If texture not ready yet, skip
-    if assets_images.get(&enemy_assets.bucket).is_none() { return; }
-    for (e, sprite_opt) in awaiting.iter_mut() {
-        // Insert texture handle and remove the marker; clear tint to white
-        let mut ecmd = commands.entity(e);
-        ecmd.insert(enemy_assets.bucket.clone());
-        ecmd.remove::<AwaitingTexture>();
-        if let Some(mut sprite) = sprite_opt {
-            sprite.color = Color::WHITE;
-        }
+    let Ok((camera, cam_tf)) = camera_q.get_single() else { return; };
+    let Some(visible) = visible_world_rect(camera, cam_tf) else { return; };
+    let heading = timer_res.heading;
+    let far = Rect::from_corners(visible.min - Vec2::splat(RECYCLE_DISTANCE), visible.max + Vec2::splat(RECYCLE_DISTANCE));
+    let ring = Rect::from_corners(visible.min - Vec2::splat(SPAWN_MARGIN), visible.max + Vec2::splat(SPAWN_MARGIN));
+    for (rank, mut tf) in enemies.iter_mut() {
+        if far.contains(tf.translation.truncate()) { continue; }
+        let Some(pos) = map.free_spot_near(pick_spawn_point(ring, heading), rank.size() * 0.5) else { continue; };
+        tf.translation.x = pos.x;
+        tf.translation.y = pos.y;
     }
 }
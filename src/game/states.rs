@@ -4,14 +4,18 @@ use bevy::prelude::*;
 pub enum GameState {
     #[default]
     Loading,
+    // Title screen after loading and when a run is abandoned from the pause menu
+    MainMenu,
     Playing,
     Paused,
     LevelUp,
+    // Treasure chest reveal; gameplay is frozen like LevelUp
+    ChestOpen,
     GameOver,
 }
 
 impl GameState {
     pub fn is_menu(&self) -> bool {
-        matches!(self, GameState::Paused | GameState::GameOver | GameState::Loading | GameState::LevelUp)
+        matches!(self, GameState::Paused | GameState::GameOver | GameState::Loading | GameState::MainMenu | GameState::LevelUp | GameState::ChestOpen)
     }
 }
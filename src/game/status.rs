@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+
+use super::combat::{kill_enemy, CombatSet};
+use super::enemy::{Enemy, EnemyHealth, EnemyRank};
+use super::states::GameState;
+
+pub struct StatusPlugin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusKind {
+    Burning,
+    Slow,
+    Freeze,
+    Poison,
+}
+
+// What re-applying a status does while it is still running
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stacking {
+    // Reset the duration only
+    Refresh,
+    // Reset the duration and add stacks up to `max`, at most once per `interval` seconds
+    Stack { max: u8, interval: f32 },
+}
+
+pub struct StatusDef {
+    pub kind: StatusKind,
+    pub duration: f32,
+    // Damage per second for each stack
+    pub dps: f32,
+    // Multiplies movement speed while active (1.0 = unaffected)
+    pub speed: f32,
+    pub stacking: Stacking,
+    pub tint: Color,
+}
+
+// Listed in tint priority: when several are active the first one found here colours the sprite
+pub const STATUS_DEFS: [StatusDef; 4] = [
+    StatusDef { kind: StatusKind::Freeze, duration: 1.5, dps: 0.0, speed: 0.0, stacking: Stacking::Refresh, tint: Color::rgb(0.55, 0.85, 1.0) },
+    StatusDef { kind: StatusKind::Burning, duration: 3.0, dps: 4.0, speed: 1.0, stacking: Stacking::Stack { max: 3, interval: 0.5 }, tint: Color::rgb(1.0, 0.45, 0.15) },
+    StatusDef { kind: StatusKind::Poison, duration: 5.0, dps: 2.0, speed: 1.0, stacking: Stacking::Stack { max: 5, interval: 0.5 }, tint: Color::rgb(0.45, 0.95, 0.35) },
+    StatusDef { kind: StatusKind::Slow, duration: 2.0, dps: 0.0, speed: 0.5, stacking: Stacking::Refresh, tint: Color::rgb(0.5, 0.55, 0.9) },
+];
+
+// How strongly the status tint replaces the sprite's own colour
+const TINT_STRENGTH: f32 = 0.6;
+
+impl StatusKind {
+    pub fn def(self) -> &'static StatusDef {
+        STATUS_DEFS.iter().find(|d| d.kind == self).expect("every status has a def")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ActiveStatus {
+    kind: StatusKind,
+    remaining: f32,
+    stacks: u8,
+    // Time until another stack may be added
+    stack_cooldown: f32,
+}
+
+/// Statuses running on an entity. Anything with this component ticks; enemies spawn with an empty one.
+#[derive(Component, Default, Debug)]
+pub struct StatusEffects(Vec<ActiveStatus>);
+
+impl StatusEffects {
+    /// Apply `stacks` of `kind` (ignored beyond the first for `Refresh` statuses).
+    /// A multi-stack hit always lifts the count to at least `stacks`, even during the stack cooldown.
+    pub fn apply(&mut self, kind: StatusKind, stacks: u8) {
+        let def = kind.def();
+        match self.0.iter_mut().find(|s| s.kind == kind) {
+            Some(active) => {
+                active.remaining = def.duration;
+                if let Stacking::Stack { max, interval } = def.stacking {
+                    active.stacks = active.stacks.max(stacks.min(max));
+                    if active.stack_cooldown <= 0.0 {
+                        active.stacks = (active.stacks + stacks).min(max);
+                        active.stack_cooldown = interval;
+                    }
+                }
+            }
+            None => {
+                let (stacks, stack_cooldown) = match def.stacking {
+                    Stacking::Refresh => (1, 0.0),
+                    Stacking::Stack { max, interval } => (stacks.clamp(1, max), interval),
+                };
+                self.0.push(ActiveStatus { kind, remaining: def.duration, stacks, stack_cooldown });
+            }
+        }
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.0.iter().any(|s| s.kind == kind)
+    }
+
+    // Product of every active status's speed factor
+    pub fn speed_multiplier(&self) -> f32 {
+        self.0.iter().map(|s| s.kind.def().speed).product()
+    }
+
+    pub fn dps(&self) -> f32 {
+        self.0.iter().map(|s| s.kind.def().dps * s.stacks as f32).sum()
+    }
+
+    pub fn tint(&self) -> Option<Color> {
+        STATUS_DEFS.iter().find(|d| self.has(d.kind)).map(|d| d.tint)
+    }
+
+    fn tick(&mut self, dt: f32) {
+        for s in self.0.iter_mut() {
+            s.remaining -= dt;
+            s.stack_cooldown -= dt;
+        }
+        self.0.retain(|s| s.remaining > 0.0);
+    }
+}
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            tick_enemy_statuses.after(CombatSet),
+            tint_enemy_statuses.after(tick_enemy_statuses),
+        ).run_if(in_state(GameState::Playing)));
+    }
+}
+
+// Damage over time is applied here and can kill; durations count down and expire
+fn tick_enemy_statuses(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut enemies: Query<(Entity, &Transform, &mut EnemyHealth, &EnemyRank, &mut StatusEffects), With<Enemy>>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, tf, mut eh, rank, mut status) in enemies.iter_mut() {
+        if status.0.is_empty() { continue; }
+        let dps = status.dps();
+        status.tick(dt);
+        if dps <= 0.0 { continue; }
+        eh.hp -= dps * dt;
+        if eh.hp <= 0.0 {
+            kill_enemy(&mut commands, entity, tf.translation.truncate(), *rank);
+        }
+    }
+}
+
+// Only enemies carry a rank, so this leaves any future player statuses alone
+fn tint_enemy_statuses(mut enemies: Query<(&StatusEffects, &EnemyRank, &mut Sprite), Changed<StatusEffects>>) {
+    for (status, rank, mut sprite) in enemies.iter_mut() {
+        let base = rank.tint();
+        let color = match status.tint() {
+            Some(tint) => {
+                let (b, t) = (Vec4::from(base.as_rgba_f32()), Vec4::from(tint.as_rgba_f32()));
+                let c = b.lerp(t, TINT_STRENGTH);
+                Color::rgba(c.x, c.y, c.z, c.w)
+            }
+            None => base,
+        };
+        if sprite.color != color { sprite.color = color; }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacking_refresh_and_expiry() {
+        let mut s = StatusEffects::default();
+        s.apply(StatusKind::Burning, 1);
+        // Re-applied every frame of contact: one extra stack per interval, capped
+        for _ in 0..60 {
+            s.apply(StatusKind::Burning, 1);
+            s.tick(0.1);
+        }
+        // Three stacks of 4 dps
+        assert!((s.dps() - 12.0).abs() < 1e-4);
+
+        s.apply(StatusKind::Slow, 1);
+        s.apply(StatusKind::Slow, 1);
+        assert!((s.speed_multiplier() - 0.5).abs() < 1e-4);
+        assert_eq!(s.tint(), Some(StatusKind::Burning.def().tint));
+
+        s.tick(2.5);
+        assert!(!s.has(StatusKind::Slow));
+        assert!(s.has(StatusKind::Burning));
+        s.tick(1.0);
+        assert!(s.0.is_empty());
+        assert_eq!(s.tint(), None);
+    }
+
+    #[test]
+    fn ring_stacks_land_after_a_base_flame_touch() {
+        // Base flame contact and the Dragonfire ring hit in the same frame, base first
+        let mut s = StatusEffects::default();
+        s.apply(StatusKind::Burning, 1);
+        s.apply(StatusKind::Burning, 3);
+        assert!((s.dps() - 12.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,235 @@
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use super::assets::UiFonts;
+use super::i18n::LocalizedText;
+use super::input::{Action, ActionSet, ActionState};
+use super::states::GameState;
+
+pub struct TouchControlsPlugin;
+
+// Screen-space radius (logical px) at which the knob stops and the stick reads full tilt
+pub const STICK_MAX_RADIUS: f32 = 64.0;
+// Fraction of the radius ignored around the anchor so a resting thumb doesn't drift
+pub const STICK_DEADZONE: f32 = 0.15;
+const KNOB_SIZE: f32 = 52.0;
+
+// Floating stick: anchored wherever a touch begins (outside on-screen buttons) while Playing
+#[derive(Resource, Default)]
+pub struct VirtualStick {
+    touch: Option<u64>,
+    anchor: Vec2,
+    knob: Vec2,
+    // World-style direction (y up), 0..1 magnitude past the deadzone; fed into `ActionState`
+    pub axis: Vec2,
+    // Set once any touch is seen; shows on-screen buttons and disables mouse-follow steering
+    pub touch_seen: bool,
+}
+
+impl VirtualStick {
+    fn release(&mut self) {
+        self.touch = None;
+        self.axis = Vec2::ZERO;
+    }
+}
+
+// Stick visuals: the base ring sits on the anchor, the knob follows the finger
+#[derive(Component)]
+enum StickPart {
+    Base,
+    Knob,
+}
+
+// On-screen stand-ins for keys mobile players don't have; only shown in `state`
+#[derive(Component)]
+struct TouchButton {
+    action: Action,
+    state: GameState,
+}
+
+impl Plugin for TouchControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VirtualStick>()
+            .add_systems(Startup, spawn_touch_controls)
+            .add_systems(PreUpdate, update_virtual_stick.after(InputSystem).before(ActionSet))
+            .add_systems(Update, (draw_virtual_stick, show_touch_buttons, touch_button_presses));
+    }
+}
+
+/// Stick reading for a finger at `finger` with the stick anchored at `anchor` (screen space, y down).
+/// Returns the deadzoned axis (y up, length 0..1) and the knob offset clamped to the max radius.
+pub fn stick_axis(anchor: Vec2, finger: Vec2) -> (Vec2, Vec2) {
+    let offset = (finger - anchor).clamp_length_max(STICK_MAX_RADIUS);
+    let tilt = offset.length() / STICK_MAX_RADIUS;
+    if tilt <= STICK_DEADZONE {
+        return (Vec2::ZERO, offset);
+    }
+    let scaled = (tilt - STICK_DEADZONE) / (1.0 - STICK_DEADZONE);
+    let dir = Vec2::new(offset.x, -offset.y).normalize();
+    (dir * scaled, offset)
+}
+
+fn update_virtual_stick(
+    touches: Res<Touches>,
+    state: Res<State<GameState>>,
+    buttons: Query<(&Node, &GlobalTransform, &ViewVisibility), With<Button>>,
+    mut stick: ResMut<VirtualStick>,
+) {
+    if !stick.touch_seen && touches.iter().next().is_some() { stick.touch_seen = true; }
+    if !matches!(state.get(), GameState::Playing) {
+        stick.release();
+        return;
+    }
+    // Keep following the finger that opened the stick until it lifts
+    if let Some(id) = stick.touch {
+        match touches.get_pressed(id) {
+            Some(touch) => {
+                let (axis, knob) = stick_axis(stick.anchor, touch.position());
+                stick.axis = axis;
+                stick.knob = stick.anchor + knob;
+            }
+            None => stick.release(),
+        }
+        return;
+    }
+    for touch in touches.iter_just_pressed() {
+        let pos = touch.position();
+        let on_button = buttons.iter().any(|(node, gtf, vis)| vis.get() && node.logical_rect(gtf).contains(pos));
+        if on_button { continue; }
+        stick.touch = Some(touch.id());
+        stick.anchor = pos;
+        stick.knob = pos;
+        stick.axis = Vec2::ZERO;
+        break;
+    }
+}
+
+// Soft-edged white disc, tinted per use through the node's background colour
+fn circle_image(size: u32) -> Image {
+    let r = size as f32 / 2.0;
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let d = Vec2::new(x as f32 + 0.5 - r, y as f32 + 0.5 - r).length();
+            let alpha = ((r - d) / 2.0).clamp(0.0, 1.0);
+            data.extend_from_slice(&[255, 255, 255, (alpha * 255.0) as u8]);
+        }
+    }
+    Image::new(
+        Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+fn spawn_touch_controls(mut commands: Commands, mut images: ResMut<Assets<Image>>, fonts: Res<UiFonts>) {
+    let disc = images.add(circle_image(128));
+    let circle = |size: f32, color: Color| ImageBundle {
+        style: Style { position_type: PositionType::Absolute, width: Val::Px(size), height: Val::Px(size), ..default() },
+        image: UiImage::new(disc.clone()),
+        background_color: BackgroundColor(color),
+        visibility: Visibility::Hidden,
+        z_index: ZIndex::Global(50),
+        ..default()
+    };
+    commands.spawn((StickPart::Base, circle(STICK_MAX_RADIUS * 2.0, Color::rgba(1.0, 1.0, 1.0, 0.18))));
+    commands.spawn((StickPart::Knob, circle(KNOB_SIZE, Color::rgba(1.0, 1.0, 1.0, 0.55))));
+
+    // Top-right button column; only the buttons for the current state are visible
+    commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            right: Val::Px(8.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        z_index: ZIndex::Global(120),
+        ..default()
+    }).with_children(|col| {
+        let buttons = [
+            (None, Action::Pause, GameState::Playing),
+            (Some("touch.codex"), Action::Codex, GameState::Paused),
+            (Some("touch.restart"), Action::Restart, GameState::GameOver),
+        ];
+        // The pause glyph needs no translation
+        for (key, action, state) in buttons {
+            col.spawn((TouchButton { action, state }, ButtonBundle {
+                style: Style {
+                    min_width: Val::Px(56.0),
+                    height: Val::Px(56.0),
+                    padding: UiRect::horizontal(Val::Px(12.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.1, 0.1, 0.15, 0.7)),
+                visibility: Visibility::Hidden,
+                ..default()
+            })).with_children(|b| {
+                let mut text = b.spawn(TextBundle::from_section(
+                    "II",
+                    fonts.body(24.0, Color::WHITE)
+                ));
+                if let Some(key) = key { text.insert(LocalizedText(key)); }
+            });
+        }
+    });
+}
+
+fn draw_virtual_stick(stick: Res<VirtualStick>, mut parts: Query<(&StickPart, &mut Style, &mut Visibility)>) {
+    if !stick.is_changed() { return; }
+    let shown = if stick.touch.is_some() { Visibility::Inherited } else { Visibility::Hidden };
+    for (part, mut style, mut vis) in parts.iter_mut() {
+        let (center, size) = match part {
+            StickPart::Base => (stick.anchor, STICK_MAX_RADIUS * 2.0),
+            StickPart::Knob => (stick.knob, KNOB_SIZE),
+        };
+        style.left = Val::Px(center.x - size / 2.0);
+        style.top = Val::Px(center.y - size / 2.0);
+        *vis = shown;
+    }
+}
+
+fn show_touch_buttons(
+    stick: Res<VirtualStick>,
+    state: Res<State<GameState>>,
+    mut buttons: Query<(&TouchButton, &mut Visibility)>,
+) {
+    for (button, mut vis) in buttons.iter_mut() {
+        let shown = stick.touch_seen && button.state == *state.get();
+        vis.set_if_neq(if shown { Visibility::Inherited } else { Visibility::Hidden });
+    }
+}
+
+fn touch_button_presses(
+    buttons: Query<(&Interaction, &TouchButton), Changed<Interaction>>,
+    mut actions: ResMut<ActionState>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            actions.press_virtual(button.action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stick_deadzone_and_clamp() {
+        let anchor = Vec2::new(100.0, 100.0);
+        assert_eq!(stick_axis(anchor, anchor + Vec2::new(5.0, 0.0)).0, Vec2::ZERO);
+        // Dragging far past the rim: full tilt, knob pinned to the radius, screen-down is world-down
+        let (axis, knob) = stick_axis(anchor, anchor + Vec2::new(0.0, 300.0));
+        assert!((axis - Vec2::new(0.0, -1.0)).length() < 1e-5);
+        assert!((knob.length() - STICK_MAX_RADIUS).abs() < 1e-3);
+    }
+}
@@ -1,12 +1,20 @@
 use bevy::prelude::*;
+use bevy::ecs::system::{EntityCommands, SystemParam};
 use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy::window::PrimaryWindow;
 
 use super::player::PlayerStats;
 use super::states::GameState;
-use super::player::{Player, PlayerAura, OrbitingFlame};
-use super::xp::PendingLevelUps;
-use super::combat::{AuraConfig, AuraTickTimer};
+use super::xp::{LevelCurve, PendingLevelUps};
+use super::evolution::{EvolutionRecipe, EvolvedWeapon, Loadout, PassiveKind, WeaponKind, EVOLUTIONS};
+use super::{reset_run, RestartRun};
+use super::chest::ChestReward;
+use super::input::{key_name, Action, ActionState, ControlScheme, InputMap, RebindListening, BINDABLE_KEYS};
+use super::assets::{LoadingProgress, UiFonts};
+use super::audio::AudioVolumes;
+use super::i18n::{Localization, LocalizedText};
+use super::settings::{Preferences, SaveSettings, SettingsResources};
+use super::upgrades::{UpgradeContext, UpgradeKind, UPGRADES};
 
 pub struct UiPlugin;
 
@@ -35,30 +43,72 @@ struct LevelUpHeaderText;
 #[derive(Component)]
 struct LevelUpChoiceText;
 
-// Tracks cumulative flame speed multiplier so newly spawned flames match existing upgrades
-#[derive(Resource, Deref, DerefMut)]
-struct FlameSpeedBuff(f32);
+// Loading screen markers
+#[derive(Component)]
+struct LoadingScreen;
+#[derive(Component)]
+struct LoadingBarFill;
+#[derive(Component)]
+struct LoadingStatusText;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(FlameSpeedBuff(1.0))
+        app.add_systems(OnEnter(GameState::Loading), spawn_loading_screen)
+            .add_systems(Update, update_loading_screen.run_if(in_state(GameState::Loading)))
+            .add_systems(OnExit(GameState::Loading), close_loading_screen)
             .add_systems(OnEnter(GameState::Playing), setup_hud)
+            .init_resource::<LevelUpFocus>()
+            .init_resource::<PauseFocus>()
+            .add_systems(OnEnter(GameState::Paused), show_pause_overlay)
+            .add_systems(OnEnter(GameState::MainMenu), (show_main_menu, close_hud))
+            .add_systems(OnEnter(GameState::GameOver), show_game_over_overlay)
+            .add_systems(OnExit(GameState::GameOver), close_game_over_overlay)
             .add_systems(OnEnter(GameState::LevelUp), levelup_spawn_overlay_now)
             .add_systems(OnExit(GameState::LevelUp), levelup_cleanup_overlay)
             .add_systems(Update, (
                 update_hud_bars.run_if(in_state(GameState::Playing).or_else(in_state(GameState::GameOver))),
                 update_hud_text.run_if(in_state(GameState::Playing).or_else(in_state(GameState::GameOver))),
                 update_fps_text,
-                show_game_over_overlay.run_if(in_state(GameState::GameOver)),
+                apply_fps_visibility,
                 levelup_show_overlay.run_if(in_state(GameState::LevelUp)),
+                levelup_gamepad_nav.before(levelup_handle_buttons).before(levelup_button_visuals).run_if(in_state(GameState::LevelUp)),
                 levelup_handle_buttons.run_if(in_state(GameState::LevelUp)),
                 responsive_levelup_overlay.run_if(in_state(GameState::LevelUp)),
                 levelup_button_visuals.run_if(in_state(GameState::LevelUp)),
-            ));
+                toggle_codex.run_if(in_state(GameState::Paused)),
+                refresh_pause_summary.run_if(in_state(GameState::Paused)),
+                refresh_codex_text.run_if(in_state(GameState::Paused)),
+                chest_reveal_overlay.run_if(in_state(GameState::ChestOpen)),
+                refresh_chest_text.run_if(in_state(GameState::ChestOpen)),
+            ))
+            .add_systems(Update, (
+                toggle_controls_panel,
+                controls_panel_buttons,
+                capture_rebind,
+                refresh_controls_text,
+                refresh_scheme_text,
+            ).chain().run_if(in_menu))
+            .add_systems(Update, (
+                toggle_settings_panel,
+                settings_panel_buttons,
+                refresh_settings_text,
+            ).chain().run_if(in_menu))
+            .add_systems(Update, (
+                pause_menu_nav,
+                pause_menu_buttons,
+            ).chain().run_if(has_menu_buttons))
+            .add_systems(OnExit(GameState::Paused), (close_pause_overlay, close_codex, close_controls_panel, close_settings_panel))
+            .add_systems(OnExit(GameState::MainMenu), (close_main_menu, close_controls_panel, close_settings_panel))
+            .add_systems(OnExit(GameState::ChestOpen), chest_cleanup_overlay);
     }
 }
 
-fn setup_hud(mut commands: Commands, existing: Query<Entity, With<HudRoot>>) {
+// The title screen shows no run stats; entering Playing builds a fresh HUD
+fn close_hud(mut commands: Commands, existing: Query<Entity, With<HudRoot>>) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+}
+
+fn setup_hud(mut commands: Commands, existing: Query<Entity, With<HudRoot>>, loc: Res<Localization>, fonts: Res<UiFonts>) {
     if existing.get_single().is_ok() { return; }
     // Root full-screen UI node
     commands.spawn((HudRoot, NodeBundle {
@@ -115,20 +165,20 @@ fn setup_hud(mut commands: Commands, existing: Query<Entity, With<HudRoot>>) {
             }));
         });
 
-        // Text rows (use Bevy's built-in default font)
+        // Text rows
         parent.spawn((HpText, TextBundle::from_section(
-            "HP: 0 / 0",
-            TextStyle { font: default(), font_size: 16.0, color: Color::WHITE }
+            loc.fmt("hud.hp", &[("hp", &0), ("max", &0)]),
+            fonts.numbers(16.0, Color::WHITE)
         )));
 
         parent.spawn((LevelText, TextBundle::from_section(
-            "Level: 1",
-            TextStyle { font: default(), font_size: 16.0, color: Color::WHITE }
+            loc.fmt("hud.level", &[("level", &1)]),
+            fonts.numbers(16.0, Color::WHITE)
         )));
 
         parent.spawn((XpText, TextBundle::from_section(
-            "XP: 0",
-            TextStyle { font: default(), font_size: 16.0, color: Color::WHITE }
+            loc.fmt("hud.xp", &[("xp", &0)]),
+            fonts.numbers(16.0, Color::WHITE)
         )));
 
         // FPS in top-right corner
@@ -143,67 +193,88 @@ fn setup_hud(mut commands: Commands, existing: Query<Entity, With<HudRoot>>) {
             ..default()
         }).with_children(|fps_node| {
             fps_node.spawn((FpsText, TextBundle::from_section(
-                "FPS: --",
-                TextStyle { font: default(), font_size: 14.0, color: Color::WHITE }
+                loc.t("hud.fps_unknown"),
+                fonts.numbers(14.0, Color::WHITE)
             )));
         });
     });
 }
 
-fn update_hud_bars(stats: Res<PlayerStats>, mut hp_fill: Query<&mut Style, With<HpBarFill>>, mut xp_fill: Query<&mut Style, (With<XpBarFill>, Without<HpBarFill>)>) {
-    if !stats.is_changed() { return; }
+fn spawn_loading_screen(mut commands: Commands, fonts: Res<UiFonts>) {
+    commands.spawn((LoadingScreen, NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::rgb(0.05, 0.04, 0.08)),
+        ..default()
+    })).with_children(|p| {
+        p.spawn((LocalizedText("loading.title"), TextBundle::from_section("", fonts.header(28.0, Color::YELLOW))));
+        p.spawn(NodeBundle {
+            style: Style { width: Val::Px(320.0), height: Val::Px(14.0), ..default() },
+            background_color: BackgroundColor(Color::rgb(0.12, 0.10, 0.18)),
+            ..default()
+        }).with_children(|bar| {
+            bar.spawn((LoadingBarFill, NodeBundle {
+                style: Style { width: Val::Percent(0.0), height: Val::Percent(100.0), ..default() },
+                background_color: BackgroundColor(Color::rgb(0.8, 0.7, 0.2)),
+                ..default()
+            }));
+        });
+        p.spawn((LoadingStatusText, TextBundle::from_section("", fonts.body(16.0, Color::WHITE)).with_text_justify(JustifyText::Center)));
+    });
+}
+
+// Failures replace the counter with the reasons so a broken deploy says what's missing
+fn update_loading_screen(
+    progress: Res<LoadingProgress>,
+    loc: Res<Localization>,
+    mut fill: Query<(&mut Style, &mut BackgroundColor), With<LoadingBarFill>>,
+    mut status: Query<&mut Text, With<LoadingStatusText>>,
+) {
+    if !progress.is_changed() && !loc.is_changed() { return; }
+    let failed = !progress.errors.is_empty();
+    if let Ok((mut style, mut color)) = fill.get_single_mut() {
+        style.width = Val::Percent(progress.fraction() * 100.0);
+        if failed { *color = BackgroundColor(Color::rgb(0.8, 0.15, 0.15)); }
+    }
+    if let Ok(mut text) = status.get_single_mut() {
+        text.sections[0].value = if failed {
+            format!("{}\n{}", loc.t("loading.failed"), progress.errors.join("\n"))
+        } else {
+            loc.fmt("loading.progress", &[("done", &progress.done), ("total", &progress.total)])
+        };
+    }
+}
+
+fn close_loading_screen(mut commands: Commands, existing: Query<Entity, With<LoadingScreen>>) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+}
+
+fn update_hud_bars(stats: Res<PlayerStats>, curve: Res<LevelCurve>, mut hp_fill: Query<&mut Style, With<HpBarFill>>, mut xp_fill: Query<&mut Style, (With<XpBarFill>, Without<HpBarFill>)>) {
+    if !stats.is_changed() && !curve.is_changed() { return; }
     let hp_ratio = if stats.max_hp > 0.0 { (stats.hp / stats.max_hp).clamp(0.0, 1.0) } else { 0.0 };
     if let Ok(mut style) = hp_fill.get_single_mut() {
         style.width = Val::Px(220.0 * hp_ratio as f32);
     }
-    // XP progress toward next level (each 100 xp per current formula)
-    let xp_into_level = stats.xp % 100; // 0..99
-    let xp_ratio = (xp_into_level as f32 / 100.0).clamp(0.0, 1.0);
+    // XP progress toward next level per the level curve
+    let (_, xp_into_level, xp_needed) = curve.progress(stats.xp);
+    let xp_ratio = (xp_into_level as f32 / xp_needed as f32).clamp(0.0, 1.0);
     if let Ok(mut style) = xp_fill.get_single_mut() {
         style.width = Val::Px(220.0 * xp_ratio);
     }
 }
 
 fn levelup_button_visuals(
-    mut q: Query<
-        (
-            &Interaction,
-            &mut BackgroundColor,
-            Option<&BtnMoreDamage>,
-            Option<&BtnMoreFlame>,
-            Option<&BtnFasterFlames>,
-        ),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut q: Query<(&Interaction, &mut BackgroundColor, &UpgradeButton), (Changed<Interaction>, With<Button>)>,
 ) {
-    for (interaction, mut bg, is_damage, is_flame, is_speed) in q.iter_mut() {
-        let (base, hover, pressed) = if is_damage.is_some() {
-            (
-                Color::rgb(0.32, 0.12, 0.12),
-                Color::rgb(0.42, 0.17, 0.17),
-                Color::rgb(0.52, 0.22, 0.22),
-            )
-        } else if is_flame.is_some() {
-            (
-                Color::rgb(0.12, 0.32, 0.12),
-                Color::rgb(0.17, 0.42, 0.17),
-                Color::rgb(0.22, 0.52, 0.22),
-            )
-        } else if is_speed.is_some() {
-            (
-                Color::rgb(0.12, 0.12, 0.32),
-                Color::rgb(0.17, 0.17, 0.42),
-                Color::rgb(0.22, 0.22, 0.52),
-            )
-        } else {
-            // Fallback palette (shouldn't happen for our buttons)
-            (
-                Color::rgb(0.2, 0.2, 0.2),
-                Color::rgb(0.3, 0.3, 0.3),
-                Color::rgb(0.4, 0.4, 0.4),
-            )
-        };
-
+    for (interaction, mut bg, button) in q.iter_mut() {
+        let [base, hover, pressed] = button.0.def().colors;
         *bg = match *interaction {
             Interaction::Pressed => BackgroundColor(pressed),
             Interaction::Hovered => BackgroundColor(hover),
@@ -214,70 +285,58 @@ fn levelup_button_visuals(
 
 fn update_hud_text(
     stats: Res<PlayerStats>,
+    loc: Res<Localization>,
     mut hp_text_q: Query<&mut Text, With<HpText>>,
     mut xp_text_q: Query<&mut Text, (With<XpText>, Without<HpText>)>,
     mut lvl_text_q: Query<&mut Text, (With<LevelText>, Without<HpText>, Without<XpText>)>,
 ) {
-    if !stats.is_changed() { return; }
+    if !stats.is_changed() && !loc.is_changed() { return; }
     if let Ok(mut text) = hp_text_q.get_single_mut() {
-        text.sections[0].value = format!("HP: {} / {}", stats.hp as i32, stats.max_hp as i32);
+        text.sections[0].value = loc.fmt("hud.hp", &[("hp", &(stats.hp as i32)), ("max", &(stats.max_hp as i32))]);
     }
     if let Ok(mut text) = lvl_text_q.get_single_mut() {
-        text.sections[0].value = format!("Level: {}", stats.level);
+        text.sections[0].value = loc.fmt("hud.level", &[("level", &stats.level)]);
     }
     if let Ok(mut text) = xp_text_q.get_single_mut() {
-        text.sections[0].value = format!("XP: {}", stats.xp);
+        text.sections[0].value = loc.fmt("hud.xp", &[("xp", &stats.xp)]);
     }
 }
 
-#[derive(Component)]
-struct GameOverOverlay;
-
 #[derive(Component)]
 struct LevelUpOverlay;
+// Level-up choice backed by an entry in the upgrade registry
 #[derive(Component)]
-struct BtnMoreDamage;
-#[derive(Component)]
-struct BtnMoreFlame;
-#[derive(Component)]
-struct BtnFasterFlames;
+struct UpgradeButton(UpgradeKind);
 
-fn show_game_over_overlay(mut commands: Commands, root: Query<Entity, With<HudRoot>>, existing: Query<Entity, With<GameOverOverlay>>) {
-    if existing.get_single().is_ok() { return; }
-    let Ok(root_entity) = root.get_single() else { return; };
-    commands.entity(root_entity).with_children(|parent| {
-        // Semi-transparent panel to indicate game over (no font usage)
-        parent.spawn((GameOverOverlay, NodeBundle {
-            style: Style {
-                width: Val::Px(260.0),
-                height: Val::Px(60.0),
-                ..default()
-            },
-            background_color: BackgroundColor(Color::rgba(0.2, 0.0, 0.0, 0.75)),
-            ..default()
-        }));
-    });
-}
+// Gamepad focus among the level-up buttons (index into UPGRADES order)
+#[derive(Resource, Default)]
+struct LevelUpFocus(usize);
 
 fn levelup_show_overlay(
     commands: Commands,
     root: Query<Entity, With<HudRoot>>,
     existing: Query<Entity, With<LevelUpOverlay>>,
+    loc: Res<Localization>,
+    fonts: Res<UiFonts>,
+    pending: Res<PendingLevelUps>,
 ) {
     if existing.get_single().is_ok() { return; }
     let Ok(root_entity) = root.get_single() else { return; };
-    spawn_levelup_overlay(commands, root_entity);
+    spawn_levelup_overlay(commands, root_entity, &loc, &fonts, pending.0);
 }
 
 fn levelup_spawn_overlay_now(
     mut commands: Commands,
     root: Query<Entity, With<HudRoot>>,
     existing: Query<Entity, With<LevelUpOverlay>>,
+    loc: Res<Localization>,
+    fonts: Res<UiFonts>,
+    pending: Res<PendingLevelUps>,
 ) {
     // Clean any stale overlay then spawn fresh
     for e in existing.iter() { commands.entity(e).despawn_recursive(); }
     if let Ok(root_entity) = root.get_single() {
-        spawn_levelup_overlay(commands, root_entity);
+        spawn_levelup_overlay(commands, root_entity, &loc, &fonts, pending.0);
     }
 }
 
@@ -285,7 +344,8 @@ fn levelup_cleanup_overlay(mut commands: Commands, existing: Query<Entity, With<
     for e in existing.iter() { commands.entity(e).despawn_recursive(); }
 }
 
-fn spawn_levelup_overlay(mut commands: Commands, root_entity: Entity) {
+// `pending` counts this pick too
+fn spawn_levelup_overlay(mut commands: Commands, root_entity: Entity, loc: &Localization, fonts: &UiFonts, pending: u32) {
     commands.entity(root_entity).with_children(|parent| {
         parent.spawn((LevelUpOverlay, NodeBundle {
             style: Style {
@@ -304,143 +364,914 @@ fn spawn_levelup_overlay(mut commands: Commands, root_entity: Entity) {
             ..default()
         })).with_children(|p| {
             p.spawn((LevelUpText, LevelUpHeaderText, TextBundle::from_section(
-                "Level Up! Choose an upgrade:",
-                TextStyle { font: default(), font_size: 22.0, color: Color::YELLOW }
+                loc.t("levelup.title"),
+                fonts.header(22.0, Color::YELLOW)
             )));
+            if pending > 1 {
+                p.spawn(TextBundle::from_section(
+                    loc.plural("levelup.pending", (pending - 1) as i64, &[]),
+                    fonts.body(14.0, Color::GRAY)
+                ));
+            }
             // Buttons (full-width by default; responsive system may adjust)
             let button_style = Style { width: Val::Percent(100.0), height: Val::Px(44.0), ..default() };
-            p.spawn((BtnMoreDamage, ButtonBundle {
-                style: button_style.clone(),
-                background_color: BackgroundColor(Color::rgb(0.32, 0.12, 0.12)),
+            for def in UPGRADES {
+                p.spawn((UpgradeButton(def.kind), ButtonBundle {
+                    style: button_style.clone(),
+                    background_color: BackgroundColor(def.colors[0]),
+                    ..default()
+                })).with_children(|b| {
+                    b.spawn((LevelUpText, LevelUpChoiceText, TextBundle::from_section(
+                        loc.t(def.label_key),
+                        fonts.body(18.0, Color::WHITE)
+                    )));
+                });
+            }
+        });
+    });
+}
+
+// Menu Up/Down (D-pad, arrows) moves focus (drawn as the hover colour); Confirm "presses" the focused
+// button so the normal handler applies it
+fn levelup_gamepad_nav(
+    actions: Res<ActionState>,
+    mut focus: ResMut<LevelUpFocus>,
+    mut buttons: Query<(&UpgradeButton, &mut Interaction, &mut BackgroundColor)>,
+) {
+    let count = buttons.iter().count();
+    if count == 0 { return; }
+    let moved = if actions.just_pressed(Action::MenuDown) {
+        focus.0 = (focus.0 + 1) % count;
+        true
+    } else if actions.just_pressed(Action::MenuUp) {
+        focus.0 = (focus.0 + count - 1) % count;
+        true
+    } else {
+        false
+    };
+    let confirm = actions.just_pressed(Action::Confirm);
+    if !moved && !confirm { return; }
+    focus.0 = focus.0.min(count - 1);
+    for (button, mut interaction, mut bg) in buttons.iter_mut() {
+        let idx = UPGRADES.iter().position(|u| u.kind == button.0).unwrap_or(0);
+        let [base, hover, _] = button.0.def().colors;
+        if idx != focus.0 {
+            *bg = BackgroundColor(base);
+            continue;
+        }
+        *bg = BackgroundColor(hover);
+        if confirm { *interaction = Interaction::Pressed; }
+    }
+}
+
+fn levelup_handle_buttons(
+    mut commands: Commands,
+    mut pending: ResMut<PendingLevelUps>,
+    mut next: ResMut<NextState<GameState>>,
+    mut ui_entities: Query<Entity, With<LevelUpOverlay>>,
+    buttons: Query<(&Interaction, &UpgradeButton), (Changed<Interaction>, With<Button>)>,
+    root_q: Query<Entity, With<HudRoot>>,
+    mut upgrades: UpgradeContext,
+    loc: Res<Localization>,
+    fonts: Res<UiFonts>,
+) {
+    let Some(kind) = buttons.iter().find(|(i, _)| **i == Interaction::Pressed).map(|(_, b)| b.0) else { return; };
+    upgrades.apply(kind);
+    // finalize selection
+    if pending.0 > 0 { pending.0 -= 1; }
+    for e in ui_entities.iter_mut() { commands.entity(e).despawn_recursive(); }
+    if pending.0 > 0 {
+        if let Ok(root) = root_q.get_single() { spawn_levelup_overlay(commands.reborrow(), root, &loc, &fonts, pending.0); }
+        next.set(GameState::LevelUp);
+    } else { next.set(GameState::Playing); }
+}
+
+#[derive(Component)]
+struct ChestOverlay;
+#[derive(Component)]
+struct ChestOverlayList;
+#[derive(Component)]
+struct ChestContinueText;
+#[derive(Component)]
+struct ChestTitleText;
+// Index into the rewards revealed so far
+#[derive(Component)]
+struct ChestRewardLine(usize);
+
+// The chest overlay's nodes plus what it needs to fill them
+#[derive(SystemParam)]
+struct ChestOverlayUi<'w, 's> {
+    root: Query<'w, 's, Entity, With<HudRoot>>,
+    overlay: Query<'w, 's, Entity, With<ChestOverlay>>,
+    list: Query<'w, 's, Entity, With<ChestOverlayList>>,
+    continue_text: Query<'w, 's, &'static mut Visibility, With<ChestContinueText>>,
+    loc: Res<'w, Localization>,
+    fonts: Res<'w, UiFonts>,
+}
+
+// Any click, Confirm key/button or touch
+#[derive(SystemParam)]
+struct AnyPress<'w> {
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    touches: Res<'w, Touches>,
+    actions: Res<'w, ActionState>,
+}
+
+impl AnyPress<'_> {
+    fn just_pressed(&self) -> bool {
+        self.actions.just_pressed(Action::Confirm) || self.mouse.just_pressed(MouseButton::Left) || self.touches.any_just_pressed()
+    }
+}
+
+// Treasure reveal: one line per granted reward, then any click/key/touch continues
+fn chest_reveal_overlay(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut reward: ResMut<ChestReward>,
+    mut ui: ChestOverlayUi,
+    press: AnyPress,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    let (loc, fonts) = (&ui.loc, &ui.fonts);
+    if ui.overlay.is_empty() {
+        let Ok(root_entity) = ui.root.get_single() else { return; };
+        commands.entity(root_entity).with_children(|parent| {
+            parent.spawn((ChestOverlay, NodeBundle {
+                style: Style {
+                    width: Val::Px(420.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(10.0),
+                    margin: UiRect::all(Val::Auto),
+                    padding: UiRect::all(Val::Px(12.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.12, 0.08, 0.0, 0.9)),
+                z_index: ZIndex::Global(100),
                 ..default()
-            })).with_children(|b| {
-                b.spawn((LevelUpText, LevelUpChoiceText, TextBundle::from_section(
-                    "+5 Aura Damage",
-                    TextStyle { font: default(), font_size: 18.0, color: Color::WHITE }
+            })).with_children(|p| {
+                p.spawn((ChestTitleText, TextBundle::from_section(
+                    chest_title(&reward, loc),
+                    fonts.header(24.0, Color::GOLD)
                 )));
+                p.spawn((ChestOverlayList, NodeBundle {
+                    style: Style { flex_direction: FlexDirection::Column, align_items: AlignItems::Center, row_gap: Val::Px(6.0), ..default() },
+                    ..default()
+                }));
+                p.spawn((ChestContinueText, LocalizedText("chest.continue"), TextBundle {
+                    visibility: Visibility::Hidden,
+                    ..TextBundle::from_section("", fonts.body(14.0, Color::GRAY))
+                }));
             });
-            p.spawn((BtnMoreFlame, ButtonBundle {
-                style: button_style.clone(),
-                background_color: BackgroundColor(Color::rgb(0.12, 0.32, 0.12)),
+        });
+        return;
+    }
+
+    if !reward.fully_revealed() {
+        if reward.reveal_timer.tick(time.delta()).just_finished() {
+            let index = reward.revealed;
+            let line = chest_line(&reward, index, loc);
+            reward.revealed += 1;
+            if let Ok(list_entity) = ui.list.get_single() {
+                commands.entity(list_entity).with_children(|l| {
+                    l.spawn((ChestRewardLine(index), TextBundle::from_section(line, ui.fonts.body(18.0, Color::WHITE))));
+                });
+            }
+            if reward.fully_revealed() {
+                for mut vis in ui.continue_text.iter_mut() { *vis = Visibility::Inherited; }
+            }
+        }
+        return;
+    }
+
+    if press.just_pressed() {
+        next.set(GameState::Playing);
+    }
+}
+
+fn chest_title(reward: &ChestReward, loc: &Localization) -> String {
+    loc.plural("chest.title", reward.total() as i64, &[])
+}
+
+fn chest_line(reward: &ChestReward, index: usize, loc: &Localization) -> String {
+    match reward.evolution {
+        Some(recipe) => loc.fmt("chest.evolution", &[("name", &loc.t(recipe.result.label_key()))]),
+        None => loc.t(reward.upgrades[index].def().label_key),
+    }
+}
+
+// Settings can't be opened over a chest today, but keep the reveal in step with the language anyway
+fn refresh_chest_text(
+    reward: Res<ChestReward>,
+    loc: Res<Localization>,
+    mut titles: Query<&mut Text, (With<ChestTitleText>, Without<ChestRewardLine>)>,
+    mut lines: Query<(&ChestRewardLine, &mut Text), Without<ChestTitleText>>,
+) {
+    if !loc.is_changed() { return; }
+    for mut text in titles.iter_mut() {
+        text.sections[0].value = chest_title(&reward, &loc);
+    }
+    for (ChestRewardLine(index), mut text) in lines.iter_mut() {
+        text.sections[0].value = chest_line(&reward, *index, &loc);
+    }
+}
+
+fn chest_cleanup_overlay(mut commands: Commands, existing: Query<Entity, With<ChestOverlay>>) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+}
+
+#[derive(Component)]
+struct CodexPanel;
+#[derive(Component)]
+struct CodexEntryText(&'static EvolutionRecipe);
+
+// Pause menu: run summary plus Resume / Settings / Restart / Main Menu / Quit
+#[derive(Component)]
+struct PauseOverlay;
+
+// Title screen: Play / Settings / Controls / Quit, shares navigation with the pause menu
+#[derive(Component)]
+struct MainMenuOverlay;
+
+// Game Over: final run summary plus Restart / Main Menu
+#[derive(Component)]
+struct GameOverOverlay;
+
+#[derive(Clone, Copy, PartialEq)]
+enum PauseChoice {
+    Play,
+    Resume,
+    Settings,
+    Controls,
+    Restart,
+    // Abandon the run and go back to the title screen
+    MainMenu,
+    // Native only; a browser tab can't close itself
+    Quit,
+}
+
+// What differs between the pause menu and the title screen; the layout is shared
+struct MenuSpec {
+    title_key: &'static str,
+    title_size: f32,
+    backdrop_alpha: f32,
+    choices: &'static [PauseChoice],
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+const PAUSE_CHOICES: &[PauseChoice] = &[PauseChoice::Resume, PauseChoice::Settings, PauseChoice::Restart, PauseChoice::MainMenu, PauseChoice::Quit];
+#[cfg(target_arch = "wasm32")]
+const PAUSE_CHOICES: &[PauseChoice] = &[PauseChoice::Resume, PauseChoice::Settings, PauseChoice::Restart, PauseChoice::MainMenu];
+
+#[cfg(not(target_arch = "wasm32"))]
+const MAIN_MENU_CHOICES: &[PauseChoice] = &[PauseChoice::Play, PauseChoice::Settings, PauseChoice::Controls, PauseChoice::Quit];
+#[cfg(target_arch = "wasm32")]
+const MAIN_MENU_CHOICES: &[PauseChoice] = &[PauseChoice::Play, PauseChoice::Settings, PauseChoice::Controls];
+
+const PAUSE_MENU: MenuSpec = MenuSpec { title_key: "pause.title", title_size: 32.0, backdrop_alpha: 0.5, choices: PAUSE_CHOICES };
+const MAIN_MENU: MenuSpec = MenuSpec { title_key: "menu.title", title_size: 40.0, backdrop_alpha: 0.7, choices: MAIN_MENU_CHOICES };
+const GAME_OVER_MENU: MenuSpec = MenuSpec {
+    title_key: "gameover.title",
+    title_size: 36.0,
+    backdrop_alpha: 0.6,
+    choices: &[PauseChoice::Restart, PauseChoice::MainMenu],
+};
+
+impl PauseChoice {
+    fn label_key(self) -> &'static str {
+        match self {
+            PauseChoice::Play => "menu.play",
+            PauseChoice::Resume => "pause.resume",
+            PauseChoice::Settings => "pause.settings",
+            PauseChoice::Controls => "menu.controls",
+            PauseChoice::Restart => "pause.restart",
+            PauseChoice::MainMenu => "pause.main_menu",
+            PauseChoice::Quit => "pause.quit",
+        }
+    }
+}
+
+// Menus (and the settings / controls panels opened from them) are live while paused or on the title screen
+fn in_menu(state: Res<State<GameState>>) -> bool {
+    matches!(state.get(), GameState::Paused | GameState::MainMenu)
+}
+
+// Screens whose buttons take pointer, keyboard and gamepad navigation; Game Over has no settings / controls panels
+fn has_menu_buttons(state: Res<State<GameState>>) -> bool {
+    matches!(state.get(), GameState::Paused | GameState::MainMenu | GameState::GameOver)
+}
+
+fn menu_choices(state: &GameState) -> &'static [PauseChoice] {
+    match state {
+        GameState::MainMenu => MAIN_MENU.choices,
+        GameState::GameOver => GAME_OVER_MENU.choices,
+        _ => PAUSE_MENU.choices,
+    }
+}
+
+// Menus and panels get their own full-screen root rather than a HUD child, so the title screen can
+// open them before any run has built a HUD
+fn spawn_screen_root<'a>(commands: &'a mut Commands, marker: impl Component, z: i32, backdrop: Color) -> EntityCommands<'a> {
+    commands.spawn((marker, NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        background_color: BackgroundColor(backdrop),
+        z_index: ZIndex::Global(z),
+        ..default()
+    }))
+}
+
+// Title, whatever `extra` adds under it, one button per choice and the version footer
+fn spawn_menu_panel(
+    commands: &mut Commands,
+    marker: impl Component,
+    spec: &MenuSpec,
+    loc: &Localization,
+    fonts: &UiFonts,
+    extra: impl FnOnce(&mut ChildBuilder),
+) {
+    spawn_screen_root(commands, marker, 90, Color::rgba(0.0, 0.0, 0.0, spec.backdrop_alpha)).with_children(|screen| {
+        screen.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(360.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(16.0)),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::rgba(0.05, 0.05, 0.08, 0.92)),
+            ..default()
+        }).with_children(|p| {
+            p.spawn((LocalizedText(spec.title_key), TextBundle::from_section("", fonts.header(spec.title_size, Color::YELLOW))));
+            extra(p);
+            for (i, choice) in spec.choices.iter().enumerate() {
+                spawn_menu_button(p, *choice, i == 0, fonts);
+            }
+            p.spawn(TextBundle::from_section(
+                loc.fmt("pause.version", &[("version", &env!("CARGO_PKG_VERSION"))]),
+                fonts.body(12.0, Color::GRAY),
+            ));
+        });
+    });
+}
+
+fn spawn_menu_button(p: &mut ChildBuilder, choice: PauseChoice, focused: bool, fonts: &UiFonts) {
+    p.spawn((PauseButton(choice), ButtonBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Px(40.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        background_color: BackgroundColor(if focused { PAUSE_BUTTON_FOCUS_BG } else { PAUSE_BUTTON_BG }),
+        ..default()
+    })).with_children(|b| {
+        b.spawn((LocalizedText(choice.label_key()), TextBundle::from_section("", fonts.body(20.0, Color::WHITE))));
+    });
+}
+
+#[derive(Component)]
+struct PauseButton(PauseChoice);
+
+// Keyboard / gamepad highlight in the pause menu
+#[derive(Resource, Default)]
+struct PauseFocus(usize);
+
+const PAUSE_BUTTON_BG: Color = Color::rgb(0.15, 0.15, 0.2);
+const PAUSE_BUTTON_FOCUS_BG: Color = Color::rgb(0.3, 0.3, 0.45);
+
+fn show_pause_overlay(
+    mut commands: Commands,
+    stats: Res<PlayerStats>,
+    loadout: Res<Loadout>,
+    loc: Res<Localization>,
+    fonts: Res<UiFonts>,
+    mut focus: ResMut<PauseFocus>,
+    existing: Query<Entity, With<PauseOverlay>>,
+) {
+    if existing.get_single().is_ok() { return; }
+    focus.0 = 0;
+    spawn_menu_panel(&mut commands, PauseOverlay, &PAUSE_MENU, &loc, &fonts, |p| {
+        p.spawn((PauseSummaryText, TextBundle::from_section(pause_summary(&stats, &loadout, &loc), fonts.body(16.0, Color::WHITE))));
+    });
+}
+
+#[derive(Component)]
+struct PauseSummaryText;
+
+fn pause_summary(stats: &PlayerStats, loadout: &Loadout, loc: &Localization) -> String {
+    let weapons: Vec<String> = WeaponKind::ALL.iter()
+        .map(|w| loc.fmt("pause.weapon_level", &[("name", &loc.t(w.label_key())), ("level", &loadout.weapon_level(*w))]))
+        .chain(EvolvedWeapon::ALL.iter().filter(|e| loadout.evolved.contains(*e)).map(|e| loc.t(e.label_key())))
+        .collect();
+    let passives: Vec<String> = PassiveKind::ALL.iter().filter(|p| loadout.passives.contains(*p)).map(|p| loc.t(p.label_key())).collect();
+    let passives = if passives.is_empty() { loc.t("pause.none") } else { passives.join(", ") };
+    [
+        loc.fmt("pause.summary", &[("level", &stats.level), ("hp", &(stats.hp as i32)), ("max", &(stats.max_hp as i32)), ("xp", &stats.xp)]),
+        loc.fmt("pause.weapons", &[("list", &weapons.join(", "))]),
+        loc.fmt("pause.passives", &[("list", &passives)]),
+    ].join("\n")
+}
+
+// Built from run state rather than a single key, so LocalizedText can't refresh it
+fn refresh_pause_summary(
+    stats: Res<PlayerStats>,
+    loadout: Res<Loadout>,
+    loc: Res<Localization>,
+    mut texts: Query<&mut Text, With<PauseSummaryText>>,
+) {
+    if !loc.is_changed() { return; }
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = pause_summary(&stats, &loadout, &loc);
+    }
+}
+
+fn show_main_menu(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    fonts: Res<UiFonts>,
+    mut focus: ResMut<PauseFocus>,
+    existing: Query<Entity, With<MainMenuOverlay>>,
+) {
+    if existing.get_single().is_ok() { return; }
+    focus.0 = 0;
+    spawn_menu_panel(&mut commands, MainMenuOverlay, &MAIN_MENU, &loc, &fonts, |_| {});
+}
+
+fn show_game_over_overlay(
+    mut commands: Commands,
+    stats: Res<PlayerStats>,
+    loadout: Res<Loadout>,
+    loc: Res<Localization>,
+    fonts: Res<UiFonts>,
+    mut focus: ResMut<PauseFocus>,
+) {
+    focus.0 = 0;
+    spawn_menu_panel(&mut commands, GameOverOverlay, &GAME_OVER_MENU, &loc, &fonts, |p| {
+        p.spawn(TextBundle::from_section(pause_summary(&stats, &loadout, &loc), fonts.body(16.0, Color::WHITE)));
+    });
+}
+
+fn close_game_over_overlay(mut commands: Commands, existing: Query<Entity, With<GameOverOverlay>>) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+}
+
+fn close_main_menu(mut commands: Commands, existing: Query<Entity, With<MainMenuOverlay>>) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+}
+
+// Up/down moves the highlight (mouse hover moves it too), Confirm presses it
+fn pause_menu_nav(
+    actions: Res<ActionState>,
+    state: Res<State<GameState>>,
+    mut focus: ResMut<PauseFocus>,
+    mut buttons: Query<(&PauseButton, &mut Interaction, &mut BackgroundColor)>,
+) {
+    let choices = menu_choices(state.get());
+    let count = choices.len();
+    let hovered = buttons.iter()
+        .find(|(_, i, _)| **i == Interaction::Hovered)
+        .and_then(|(b, _, _)| choices.iter().position(|c| *c == b.0));
+    if let Some(idx) = hovered {
+        focus.0 = idx;
+    } else if actions.just_pressed(Action::MenuDown) {
+        focus.0 = (focus.0 + 1) % count;
+    } else if actions.just_pressed(Action::MenuUp) {
+        focus.0 = (focus.0 + count - 1) % count;
+    }
+    let confirm = actions.just_pressed(Action::Confirm);
+    for (button, mut interaction, mut bg) in buttons.iter_mut() {
+        let focused = choices.get(focus.0) == Some(&button.0);
+        bg.0 = if focused { PAUSE_BUTTON_FOCUS_BG } else { PAUSE_BUTTON_BG };
+        if focused && confirm { *interaction = Interaction::Pressed; }
+    }
+}
+
+fn pause_menu_buttons(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut actions: ResMut<ActionState>,
+    mut next: ResMut<NextState<GameState>>,
+    mut restart: EventWriter<RestartRun>,
+    mut exit: EventWriter<bevy::app::AppExit>,
+) {
+    let Some(choice) = buttons.iter().find(|(i, _)| **i == Interaction::Pressed).map(|(_, b)| b.0) else { return; };
+    match choice {
+        PauseChoice::Play | PauseChoice::Resume => next.set(GameState::Playing),
+        PauseChoice::Settings => actions.press_virtual(Action::Settings),
+        PauseChoice::Controls => actions.press_virtual(Action::Controls),
+        PauseChoice::Restart => { restart.send(RestartRun); }
+        PauseChoice::MainMenu => {
+            commands.add(reset_run);
+            next.set(GameState::MainMenu);
+        }
+        PauseChoice::Quit => { exit.send(bevy::app::AppExit); }
+    }
+}
+
+fn close_pause_overlay(mut commands: Commands, existing: Query<Entity, With<PauseOverlay>>) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+}
+
+// Codex (C while paused): lists evolution recipes and how close the current run is to each
+fn toggle_codex(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    root: Query<Entity, With<HudRoot>>,
+    existing: Query<Entity, With<CodexPanel>>,
+    loadout: Res<Loadout>,
+    loc: Res<Localization>,
+    fonts: Res<UiFonts>,
+) {
+    if !actions.just_pressed(Action::Codex) { return; }
+    if let Ok(panel) = existing.get_single() {
+        commands.entity(panel).despawn_recursive();
+        return;
+    }
+    let Ok(root_entity) = root.get_single() else { return; };
+    commands.entity(root_entity).with_children(|parent| {
+        parent.spawn((CodexPanel, NodeBundle {
+            style: Style {
+                width: Val::Px(520.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                margin: UiRect::all(Val::Auto),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.86)),
+            z_index: ZIndex::Global(100),
+            ..default()
+        })).with_children(|p| {
+            p.spawn((LocalizedText("codex.title"), TextBundle::from_section(
+                "",
+                fonts.header(22.0, Color::YELLOW)
+            )));
+            for recipe in EVOLUTIONS {
+                p.spawn((CodexEntryText(recipe), TextBundle::from_section(
+                    codex_entry(recipe, &loadout, &loc),
+                    fonts.body(16.0, Color::WHITE)
+                )));
+            }
+        });
+    });
+}
+
+fn codex_entry(recipe: &EvolutionRecipe, loadout: &Loadout, loc: &Localization) -> String {
+    let status = if loadout.evolved.contains(&recipe.result) {
+        "codex.evolved"
+    } else if loadout.is_eligible(recipe) {
+        "codex.ready"
+    } else {
+        "codex.locked"
+    };
+    let entry = loc.fmt("codex.entry", &[("name", &loc.t(recipe.result.label_key())), ("status", &loc.t(status))]);
+    format!("{entry}\n{}", loc.t(recipe.description_key))
+}
+
+fn refresh_codex_text(
+    loadout: Res<Loadout>,
+    loc: Res<Localization>,
+    mut texts: Query<(&CodexEntryText, &mut Text)>,
+) {
+    if !loc.is_changed() { return; }
+    for (CodexEntryText(recipe), mut text) in texts.iter_mut() {
+        text.sections[0].value = codex_entry(recipe, &loadout, &loc);
+    }
+}
+
+fn close_codex(mut commands: Commands, existing: Query<Entity, With<CodexPanel>>) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+}
+
+#[derive(Component)]
+struct ControlsPanel;
+#[derive(Component)]
+struct RebindRow(Action);
+#[derive(Component)]
+struct RebindRowText(Action);
+#[derive(Component)]
+struct ResetBindingsButton;
+#[derive(Component)]
+struct SchemeButton;
+#[derive(Component)]
+struct SchemeText;
+
+// Controls (K while paused or on the title screen): click an action, then press the new key; Esc cancels
+fn toggle_controls_panel(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    existing: Query<Entity, With<ControlsPanel>>,
+    fonts: Res<UiFonts>,
+) {
+    if !actions.just_pressed(Action::Controls) { return; }
+    if let Ok(panel) = existing.get_single() {
+        commands.entity(panel).despawn_recursive();
+        return;
+    }
+    spawn_screen_root(&mut commands, ControlsPanel, 110, Color::NONE).with_children(|parent| {
+        parent.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(420.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.86)),
+            ..default()
+        }).with_children(|p| {
+            p.spawn((LocalizedText("controls.title"), TextBundle::from_section(
+                "",
+                fonts.header(20.0, Color::YELLOW)
+            )));
+            let row_style = Style { width: Val::Percent(100.0), height: Val::Px(26.0), padding: UiRect::horizontal(Val::Px(6.0)), align_items: AlignItems::Center, ..default() };
+            p.spawn((SchemeButton, ButtonBundle {
+                style: row_style.clone(),
+                background_color: BackgroundColor(Color::rgb(0.1, 0.2, 0.3)),
                 ..default()
             })).with_children(|b| {
-                b.spawn((LevelUpText, LevelUpChoiceText, TextBundle::from_section(
-                    "+1 Flame",
-                    TextStyle { font: default(), font_size: 18.0, color: Color::WHITE }
+                b.spawn((SchemeText, TextBundle::from_section(
+                    "",
+                    fonts.body(16.0, Color::WHITE)
                 )));
             });
-            p.spawn((BtnFasterFlames, ButtonBundle {
-                style: button_style,
-                background_color: BackgroundColor(Color::rgb(0.12, 0.12, 0.32)),
+            for action in Action::ALL {
+                p.spawn((RebindRow(action), ButtonBundle {
+                    style: row_style.clone(),
+                    background_color: BackgroundColor(Color::rgb(0.15, 0.15, 0.2)),
+                    ..default()
+                })).with_children(|b| {
+                    b.spawn((RebindRowText(action), TextBundle::from_section(
+                        "",
+                        fonts.body(16.0, Color::WHITE)
+                    )));
+                });
+            }
+            p.spawn((ResetBindingsButton, ButtonBundle {
+                style: row_style,
+                background_color: BackgroundColor(Color::rgb(0.3, 0.15, 0.1)),
                 ..default()
             })).with_children(|b| {
-                b.spawn((LevelUpText, LevelUpChoiceText, TextBundle::from_section(
-                    "+20% Flame Speed (and tick)",
-                    TextStyle { font: default(), font_size: 18.0, color: Color::WHITE }
+                b.spawn((LocalizedText("controls.reset"), TextBundle::from_section(
+                    "",
+                    fonts.body(16.0, Color::WHITE)
                 )));
             });
         });
     });
 }
 
-fn levelup_handle_buttons(
-    mut commands: Commands,
-    mut pending: ResMut<PendingLevelUps>,
-    mut next: ResMut<NextState<GameState>>,
-    mut cfg: ResMut<AuraConfig>,
-    player_q: Query<Entity, With<Player>>, // parent to attach new flames
-    mut flames_q: Query<&mut OrbitingFlame, With<OrbitingFlame>>,
-    flames_count_q: Query<(), With<OrbitingFlame>>,
-    mut ui_entities: Query<Entity, With<LevelUpOverlay>>,
-    // Separate queries to detect which specific button was pressed
-    q_more_damage: Query<&Interaction, (Changed<Interaction>, With<Button>, With<BtnMoreDamage>)>,
-    q_more_flame: Query<&Interaction, (Changed<Interaction>, With<Button>, With<BtnMoreFlame>)>,
-    q_faster: Query<&Interaction, (Changed<Interaction>, With<Button>, With<BtnFasterFlames>)>,
-    player_assets: Res<crate::game::assets::PlayerAssets>,
-    mut aura_timer: ResMut<AuraTickTimer>,
-    root_q: Query<Entity, With<HudRoot>>,
-    mut flame_speed: ResMut<FlameSpeedBuff>,
+fn controls_panel_buttons(
+    rows: Query<(&Interaction, &RebindRow), Changed<Interaction>>,
+    reset: Query<&Interaction, (Changed<Interaction>, With<ResetBindingsButton>)>,
+    scheme_button: Query<&Interaction, (Changed<Interaction>, With<SchemeButton>)>,
+    mut listening: ResMut<RebindListening>,
+    mut map: ResMut<InputMap>,
+    mut scheme: ResMut<ControlScheme>,
+    mut save: EventWriter<SaveSettings>,
 ) {
-    // Option 1: +5 aura damage
-    for interaction in q_more_damage.iter() {
-        if *interaction == Interaction::Pressed {
-            cfg.damage_tick += 5.0;
-            // finalize selection
-            if pending.0 > 0 { pending.0 -= 1; }
-            for e in ui_entities.iter_mut() { commands.entity(e).despawn_recursive(); }
-            if pending.0 > 0 {
-                if let Ok(root) = root_q.get_single() { spawn_levelup_overlay(commands.reborrow(), root); }
-                next.set(GameState::LevelUp);
-            } else { next.set(GameState::Playing); }
-            return;
-        }
+    for (interaction, row) in rows.iter() {
+        if *interaction == Interaction::Pressed { listening.0 = Some(row.0); }
+    }
+    // Click cycles through the control schemes
+    if scheme_button.iter().any(|i| *i == Interaction::Pressed) {
+        *scheme = scheme.next();
+        save.send_default();
     }
+    if reset.iter().any(|i| *i == Interaction::Pressed) {
+        *map = InputMap::default();
+        listening.0 = None;
+        save.send_default();
+    }
+}
+
+// Replaces the action's primary key with the next bindable key pressed
+fn capture_rebind(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut listening: ResMut<RebindListening>,
+    mut map: ResMut<InputMap>,
+    mut save: EventWriter<SaveSettings>,
+) {
+    let Some(action) = listening.0 else { return; };
+    let Some(key) = kb.get_just_pressed().copied().find(|k| BINDABLE_KEYS.contains(k)) else { return; };
+    listening.0 = None;
+    if key == KeyCode::Escape { return; }
+    map.rebind(action, key);
+    save.send_default();
+}
+
+fn refresh_controls_text(
+    map: Res<InputMap>,
+    listening: Res<RebindListening>,
+    loc: Res<Localization>,
+    added: Query<(), Added<RebindRowText>>,
+    mut texts: Query<(&RebindRowText, &mut Text)>,
+) {
+    if !map.is_changed() && !listening.is_changed() && !loc.is_changed() && added.is_empty() { return; }
+    for (row, mut text) in texts.iter_mut() {
+        let action = loc.t(&row.0.l10n_key());
+        text.sections[0].value = if listening.0 == Some(row.0) {
+            loc.fmt("controls.listening", &[("action", &action)])
+        } else {
+            let keys: Vec<String> = map.keys_for(row.0).iter().map(|k| key_name(*k)).collect();
+            loc.fmt("controls.binding", &[("action", &action), ("keys", &keys.join(" / "))])
+        };
+    }
+}
+
+fn refresh_scheme_text(
+    scheme: Res<ControlScheme>,
+    loc: Res<Localization>,
+    added: Query<(), Added<SchemeText>>,
+    mut texts: Query<&mut Text, With<SchemeText>>,
+) {
+    if !scheme.is_changed() && !loc.is_changed() && added.is_empty() { return; }
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = loc.fmt("controls.scheme", &[("scheme", &loc.t(&scheme.l10n_key()))]);
+    }
+}
+
+fn close_controls_panel(
+    mut commands: Commands,
+    existing: Query<Entity, With<ControlsPanel>>,
+    mut listening: ResMut<RebindListening>,
+) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+    listening.0 = None;
+}
+
+// Settings (O while paused or on the title screen): one row per option with < > buttons; changes apply and save immediately
+#[derive(Clone, Copy, PartialEq)]
+enum SettingKind {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    ShowFps,
+    Scheme,
+    ScreenShake,
+    DamageNumbers,
+    Language,
+}
+
+const SETTING_ROWS: [SettingKind; 8] = [
+    SettingKind::MasterVolume, SettingKind::MusicVolume, SettingKind::SfxVolume, SettingKind::ShowFps,
+    SettingKind::Scheme, SettingKind::ScreenShake, SettingKind::DamageNumbers, SettingKind::Language,
+];
+
+#[derive(Component)]
+struct SettingsPanel;
+#[derive(Component)]
+struct SettingText(SettingKind);
+#[derive(Component)]
+struct SettingAdjust(SettingKind, i32);
+
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T, dir: i32) -> T {
+    let i = all.iter().position(|x| *x == current).unwrap_or(0) as i32;
+    all[(i + dir).rem_euclid(all.len() as i32) as usize]
+}
+
+fn setting_text(kind: SettingKind, volumes: &AudioVolumes, scheme: ControlScheme, prefs: &Preferences, loc: &Localization) -> String {
+    let pct = |v: f32| format!("{:.0}%", v * 100.0);
+    let on_off = |b: bool| loc.t(if b { "settings.on" } else { "settings.off" });
+    let (key, value) = match kind {
+        SettingKind::MasterVolume => ("settings.master_volume", pct(volumes.master)),
+        SettingKind::MusicVolume => ("settings.music_volume", pct(volumes.music)),
+        SettingKind::SfxVolume => ("settings.sfx_volume", pct(volumes.sfx)),
+        SettingKind::ShowFps => ("settings.show_fps", on_off(prefs.show_fps)),
+        SettingKind::Scheme => ("settings.scheme", loc.t(&scheme.l10n_key())),
+        SettingKind::ScreenShake => ("settings.screen_shake", pct(prefs.screen_shake)),
+        SettingKind::DamageNumbers => ("settings.damage_numbers", on_off(prefs.damage_numbers)),
+        SettingKind::Language => ("settings.language", loc.language_name(loc.language())),
+    };
+    loc.fmt(key, &[("value", &value)])
+}
 
-    // Option 2: +1 flame and redistribute
-    for interaction in q_more_flame.iter() {
-        if *interaction == Interaction::Pressed {
-            if let Ok(player_entity) = player_q.get_single() {
-                commands.entity(player_entity).with_children(|p| {
-                    let angle = 0.0;
-                    // Ensure new flame speed matches existing upgrades via FlameSpeedBuff
-                    let base_speed = 1.8 * **flame_speed;
-                    p.spawn((PlayerAura, OrbitingFlame { angle, radius: cfg.radius, speed: base_speed, contact_radius: 16.0, contact_damage: 5.0 }, SpriteBundle {
-                        texture: player_assets.flame.clone(),
-                        sprite: Sprite { color: Color::rgba(1.0, 1.0, 1.0, 0.0), custom_size: Some(Vec2::splat(32.0)), ..default() },
-                        transform: Transform::from_xyz(angle.cos() * cfg.radius, angle.sin() * cfg.radius, 0.0),
-                        ..default()
-                    }));
+fn toggle_settings_panel(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    existing: Query<Entity, With<SettingsPanel>>,
+    fonts: Res<UiFonts>,
+) {
+    if !actions.just_pressed(Action::Settings) { return; }
+    if let Ok(panel) = existing.get_single() {
+        commands.entity(panel).despawn_recursive();
+        return;
+    }
+    spawn_screen_root(&mut commands, SettingsPanel, 115, Color::NONE).with_children(|parent| {
+        parent.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(420.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.86)),
+            ..default()
+        }).with_children(|p| {
+            p.spawn((LocalizedText("settings.title"), TextBundle::from_section(
+                "",
+                fonts.header(22.0, Color::YELLOW)
+            )));
+            for kind in SETTING_ROWS {
+                p.spawn(NodeBundle {
+                    style: Style { width: Val::Percent(100.0), align_items: AlignItems::Center, column_gap: Val::Px(6.0), ..default() },
+                    ..default()
+                }).with_children(|row| {
+                    for (label, dir) in [("<", -1), (">", 1)] {
+                        if dir > 0 {
+                            row.spawn((SettingText(kind), TextBundle {
+                                text: Text::from_section("", fonts.body(16.0, Color::WHITE)),
+                                style: Style { flex_grow: 1.0, ..default() },
+                                ..default()
+                            }));
+                        }
+                        row.spawn((SettingAdjust(kind, dir), ButtonBundle {
+                            style: Style { width: Val::Px(28.0), height: Val::Px(26.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, ..default() },
+                            background_color: BackgroundColor(Color::rgb(0.15, 0.15, 0.2)),
+                            ..default()
+                        })).with_children(|b| {
+                            b.spawn(TextBundle::from_section(label, fonts.body(16.0, Color::WHITE)));
+                        });
+                    }
                 });
-                // Redistribute angles evenly among all flames
-                let n = flames_count_q.iter().count().max(1) as f32;
-                let mut idx = 0usize;
-                for mut of in flames_q.iter_mut() {
-                    of.angle = idx as f32 / n * std::f32::consts::TAU;
-                    of.radius = cfg.radius;
-                    idx += 1;
-                }
             }
-            if pending.0 > 0 { pending.0 -= 1; }
-            for e in ui_entities.iter_mut() { commands.entity(e).despawn_recursive(); }
-            if pending.0 > 0 {
-                if let Ok(root) = root_q.get_single() { spawn_levelup_overlay(commands.reborrow(), root); }
-                next.set(GameState::LevelUp);
-            } else { next.set(GameState::Playing); }
-            return;
-        }
-    }
+        });
+    });
+}
 
-    // Option 3: +20% flame speed
-    for interaction in q_faster.iter() {
-        if *interaction == Interaction::Pressed {
-            for mut of in flames_q.iter_mut() {
-                of.speed *= 1.20;
+fn settings_panel_buttons(
+    buttons: Query<(&Interaction, &SettingAdjust), Changed<Interaction>>,
+    mut res: SettingsResources,
+    mut save: EventWriter<SaveSettings>,
+    loc: Res<Localization>,
+    fonts: Res<UiFonts>,
+) {
+    for (interaction, SettingAdjust(kind, dir)) in buttons.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        let mut s = res.snapshot();
+        let step = |v: f32, by: f32| (((v + by * *dir as f32) / by).round() * by).clamp(0.0, 1.0);
+        match kind {
+            SettingKind::MasterVolume => s.volumes.master = step(s.volumes.master, 0.1),
+            SettingKind::MusicVolume => s.volumes.music = step(s.volumes.music, 0.1),
+            SettingKind::SfxVolume => s.volumes.sfx = step(s.volumes.sfx, 0.1),
+            SettingKind::ShowFps => s.prefs.show_fps = !s.prefs.show_fps,
+            SettingKind::Scheme => s.scheme = cycle(&ControlScheme::ALL, s.scheme, *dir),
+            SettingKind::ScreenShake => s.prefs.screen_shake = step(s.prefs.screen_shake, 0.25),
+            SettingKind::DamageNumbers => s.prefs.damage_numbers = !s.prefs.damage_numbers,
+            SettingKind::Language => {
+                let ids = loc.languages(fonts.ascii_only());
+                s.prefs.language = cycle(&ids, loc.language(), *dir).to_string();
             }
-            // Update global buff so future flames inherit the current speed multiplier
-            **flame_speed *= 1.20;
-            // Also decrease aura damage tick interval by 20% (faster ticks), with a reasonable floor
-            let current = aura_timer.duration_secs();
-            let new = (current * 0.8).max(0.05);
-            aura_timer.set_duration_secs(new);
-            if pending.0 > 0 { pending.0 -= 1; }
-            for e in ui_entities.iter_mut() { commands.entity(e).despawn_recursive(); }
-            if pending.0 > 0 {
-                if let Ok(root) = root_q.get_single() { spawn_levelup_overlay(commands.reborrow(), root); }
-                next.set(GameState::LevelUp);
-            } else { next.set(GameState::Playing); }
-            return;
         }
+        res.store(s);
+        save.send_default();
+    }
+}
+
+fn refresh_settings_text(
+    volumes: Res<AudioVolumes>,
+    scheme: Res<ControlScheme>,
+    prefs: Res<Preferences>,
+    loc: Res<Localization>,
+    added: Query<(), Added<SettingText>>,
+    mut texts: Query<(&SettingText, &mut Text)>,
+) {
+    if !volumes.is_changed() && !scheme.is_changed() && !prefs.is_changed() && !loc.is_changed() && added.is_empty() { return; }
+    for (SettingText(kind), mut text) in texts.iter_mut() {
+        text.sections[0].value = setting_text(*kind, &volumes, *scheme, &prefs, &loc);
+    }
+}
+
+fn close_settings_panel(mut commands: Commands, existing: Query<Entity, With<SettingsPanel>>) {
+    for e in existing.iter() { commands.entity(e).despawn_recursive(); }
+}
+
+fn apply_fps_visibility(prefs: Res<Preferences>, mut q: Query<&mut Visibility, With<FpsText>>, added: Query<(), Added<FpsText>>) {
+    if !prefs.is_changed() && added.is_empty() { return; }
+    for mut vis in q.iter_mut() {
+        *vis = if prefs.show_fps { Visibility::Inherited } else { Visibility::Hidden };
     }
 }
 
 fn update_fps_text(
     diagnostics: Res<DiagnosticsStore>,
+    loc: Res<Localization>,
     mut q: Query<&mut Text, With<FpsText>>,
 ) {
     if let Ok(mut text) = q.get_single_mut() {
         if let Some(fps_diag) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS) {
             if let Some(avg) = fps_diag.smoothed() {
-                text.sections[0].value = format!("FPS: {:.0}", avg.max(0.0));
+                text.sections[0].value = loc.fmt("hud.fps", &[("fps", &format!("{:.0}", avg.max(0.0)))]);
             }
         }
     }
@@ -449,7 +1280,7 @@ fn update_fps_text(
 fn responsive_levelup_overlay(
     windows: Query<&Window, With<PrimaryWindow>>,
     mut overlay_q: Query<&mut Style, With<LevelUpOverlay>>,
-    mut button_q: Query<&mut Style, (With<Button>, Or<(With<BtnMoreDamage>, With<BtnMoreFlame>, With<BtnFasterFlames>)>, Without<LevelUpOverlay>)>,
+    mut button_q: Query<&mut Style, (With<Button>, With<UpgradeButton>, Without<LevelUpOverlay>)>,
     mut header_q: Query<&mut Text, (With<LevelUpHeaderText>, Without<LevelUpChoiceText>)>,
     mut choice_q: Query<&mut Text, (With<LevelUpChoiceText>, Without<LevelUpHeaderText>)>,
 ) {
@@ -467,3 +1298,110 @@ fn responsive_levelup_overlay(
     if let Ok(mut t) = header_q.get_single_mut() { t.sections[0].style.font_size = header_fs; }
     for mut t in choice_q.iter_mut() { t.sections[0].style.font_size = choice_fs; }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::gamepad::{GamepadButtonChangedEvent, GamepadButtonType, GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo};
+    use bevy::input::InputPlugin;
+    use crate::game::camera::CameraRig;
+    use crate::game::damage_numbers::DamageNumber;
+    use crate::game::flowfield::{FlowField, FlowFieldPlugin};
+    use crate::game::input::InputMapPlugin;
+    use crate::game::map::MapObstacles;
+    use crate::game::particles::{Particle, EMBER_BURST};
+    use crate::game::player::Player;
+    use crate::game::restart_game;
+    use crate::game::spawn::Dying;
+    use crate::game::touch::VirtualStick;
+
+    // Game Over buttons driven by a synthetic pad through the real action map
+    fn game_over_app() -> (App, Gamepad) {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputMapPlugin))
+            .init_resource::<VirtualStick>()
+            .init_resource::<PauseFocus>()
+            .init_resource::<CameraRig>()
+            .insert_state(GameState::GameOver)
+            .add_event::<RestartRun>()
+            .add_systems(Update, (pause_menu_nav, pause_menu_buttons).chain().run_if(has_menu_buttons));
+        for choice in GAME_OVER_MENU.choices {
+            app.world.spawn((PauseButton(*choice), Interaction::None, BackgroundColor(PAUSE_BUTTON_BG)));
+        }
+        let pad = Gamepad::new(0);
+        app.world.send_event(GamepadEvent::Connection(GamepadConnectionEvent::new(
+            pad,
+            GamepadConnection::Connected(GamepadInfo { name: "Synthetic Pad".into() }),
+        )));
+        app.update();
+        (app, pad)
+    }
+
+    fn tap(app: &mut App, pad: Gamepad, button: GamepadButtonType) {
+        app.world.send_event(GamepadEvent::Button(GamepadButtonChangedEvent::new(pad, button, 1.0)));
+        app.update();
+        app.world.send_event(GamepadEvent::Button(GamepadButtonChangedEvent::new(pad, button, 0.0)));
+        app.update();
+    }
+
+    #[test]
+    fn dpad_and_a_pick_game_over_main_menu() {
+        let (mut app, pad) = game_over_app();
+        tap(&mut app, pad, GamepadButtonType::DPadDown);
+        assert_eq!(app.world.resource::<PauseFocus>().0, 1, "D-pad down moves to Main Menu");
+        tap(&mut app, pad, GamepadButtonType::South);
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::MainMenu);
+        assert!(app.world.resource::<Events<RestartRun>>().is_empty(), "(A) confirms the focus instead of restarting");
+    }
+
+    #[test]
+    fn a_restarts_from_the_first_game_over_button() {
+        let (mut app, pad) = game_over_app();
+        tap(&mut app, pad, GamepadButtonType::South);
+        assert_eq!(app.world.resource::<Events<RestartRun>>().len(), 1);
+    }
+
+    fn count<T: Component>(app: &mut App) -> usize {
+        app.world.query_filtered::<(), With<T>>().iter(&app.world).count()
+    }
+
+    #[test]
+    fn restart_from_game_over_leaves_nothing_of_the_old_run() {
+        let mut app = App::new();
+        app.add_plugins(FlowFieldPlugin)
+            .insert_state(GameState::Playing)
+            .init_resource::<Time>()
+            .init_resource::<ActionState>()
+            .init_resource::<CameraRig>()
+            .insert_resource(MapObstacles { boxes: vec![Rect::from_center_half_size(Vec2::new(200.0, 0.0), Vec2::splat(40.0))], bounds: None })
+            .add_event::<RestartRun>()
+            .add_systems(Update, restart_game)
+            .add_systems(OnExit(GameState::GameOver), close_game_over_overlay);
+        app.world.spawn((Player, Transform::default()));
+        app.update();
+        let near_rock = Vec2::new(300.0, 0.0);
+        assert!(app.world.resource::<FlowField>().sample(near_rock).is_some(), "the old run steers around the rock");
+
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::GameOver);
+        app.update();
+        app.world.spawn(GameOverOverlay);
+        app.world.spawn(Dying);
+        app.world.spawn(Particle::new(&EMBER_BURST, 1.0, Vec2::ZERO));
+        app.world.spawn(DamageNumber::default());
+        let mut rig = app.world.resource_mut::<CameraRig>();
+        rig.focus = Vec2::new(500.0, -200.0);
+        rig.trauma = 0.8;
+
+        app.world.send_event(RestartRun);
+        app.update();
+        app.update();
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Playing);
+        assert_eq!(count::<GameOverOverlay>(&mut app), 0);
+        assert_eq!(count::<Dying>(&mut app), 0);
+        assert_eq!(count::<Particle>(&mut app), 0);
+        assert_eq!(count::<DamageNumber>(&mut app), 0);
+        let rig = app.world.resource::<CameraRig>();
+        assert_eq!((rig.focus, rig.trauma), (Vec2::ZERO, 0.0));
+        assert_eq!(app.world.resource::<FlowField>().sample(near_rock), None, "the flow field starts over");
+    }
+}
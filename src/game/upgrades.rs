@@ -0,0 +1,173 @@
+use bevy::core::FrameCount;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use super::animation::{Animator, ClipId};
+use super::assets::PlayerAssets;
+use super::combat::{AuraConfig, AuraTickTimer};
+use super::evolution::{DragonfireRing, EvolvedWeapon, Loadout, PassiveKind, WeaponKind};
+use super::player::{OrbitingFlame, Player, PlayerAura, FLAME_SHEET};
+
+pub struct UpgradesPlugin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpgradeKind {
+    AuraDamage,
+    ExtraFlame,
+    FlameSpeed,
+}
+
+pub struct UpgradeDef {
+    pub kind: UpgradeKind,
+    // Locale key of the button text
+    pub label_key: &'static str,
+    // Button palette: base / hover / pressed
+    pub colors: [Color; 3],
+}
+
+// Registry of every upgrade that level-ups and chests can hand out
+pub const UPGRADES: &[UpgradeDef] = &[
+    UpgradeDef {
+        kind: UpgradeKind::AuraDamage,
+        label_key: "upgrade.aura_damage",
+        colors: [Color::rgb(0.32, 0.12, 0.12), Color::rgb(0.42, 0.17, 0.17), Color::rgb(0.52, 0.22, 0.22)],
+    },
+    UpgradeDef {
+        kind: UpgradeKind::ExtraFlame,
+        label_key: "upgrade.extra_flame",
+        colors: [Color::rgb(0.12, 0.32, 0.12), Color::rgb(0.17, 0.42, 0.17), Color::rgb(0.22, 0.52, 0.22)],
+    },
+    UpgradeDef {
+        kind: UpgradeKind::FlameSpeed,
+        label_key: "upgrade.flame_speed",
+        colors: [Color::rgb(0.12, 0.12, 0.32), Color::rgb(0.17, 0.17, 0.42), Color::rgb(0.22, 0.22, 0.52)],
+    },
+];
+
+impl UpgradeKind {
+    pub fn def(self) -> &'static UpgradeDef {
+        UPGRADES.iter().find(|u| u.kind == self).expect("every UpgradeKind is registered")
+    }
+}
+
+// Tracks cumulative flame speed multiplier so newly spawned flames match existing upgrades
+#[derive(Resource, Deref, DerefMut)]
+pub struct FlameSpeedBuff(pub f32);
+
+impl Plugin for UpgradesPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FlameSpeedBuff(1.0));
+    }
+}
+
+// Everything an upgrade may touch, bundled so any system can apply registry upgrades
+#[derive(SystemParam)]
+pub struct UpgradeContext<'w, 's> {
+    commands: Commands<'w, 's>,
+    cfg: ResMut<'w, AuraConfig>,
+    aura_timer: ResMut<'w, AuraTickTimer>,
+    flame_speed: ResMut<'w, FlameSpeedBuff>,
+    loadout: ResMut<'w, Loadout>,
+    player_assets: Res<'w, PlayerAssets>,
+    player_q: Query<'w, 's, Entity, With<Player>>,
+    flames_q: Query<'w, 's, &'static mut OrbitingFlame>,
+    frame: Res<'w, FrameCount>,
+    // Flames spawned by earlier calls this frame; their commands haven't landed in `flames_q` yet
+    pending_flames: Local<'s, (u32, Vec<(Entity, OrbitingFlame)>)>,
+}
+
+impl UpgradeContext<'_, '_> {
+    pub fn apply(&mut self, kind: UpgradeKind) {
+        // Commands are flushed between frames, so anything pending from an older frame is in `flames_q` now
+        if self.pending_flames.0 != self.frame.0 {
+            *self.pending_flames = (self.frame.0, Vec::new());
+        }
+        match kind {
+            UpgradeKind::AuraDamage => {
+                self.cfg.damage_tick += 5.0;
+                self.loadout.passives.insert(PassiveKind::Kindling);
+            }
+            UpgradeKind::ExtraFlame => {
+                self.loadout.level_up_weapon(WeaponKind::OrbitingFlames);
+                let Ok(player_entity) = self.player_q.get_single() else { return; };
+                // Redistribute angles evenly among existing and pending flames plus the new one
+                let existing = self.flames_q.iter().count();
+                let n = existing + self.pending_flames.1.len() + 1;
+                for (idx, mut of) in self.flames_q.iter_mut().enumerate() {
+                    of.angle = idx as f32 / n as f32 * std::f32::consts::TAU;
+                    of.radius = self.cfg.radius;
+                }
+                for (idx, (entity, of)) in self.pending_flames.1.iter_mut().enumerate() {
+                    of.angle = (existing + idx) as f32 / n as f32 * std::f32::consts::TAU;
+                    self.commands.entity(*entity).insert(*of);
+                }
+                let angle = (n - 1) as f32 / n as f32 * std::f32::consts::TAU;
+                // Ensure new flame speed matches existing upgrades via FlameSpeedBuff
+                let speed = 1.8 * **self.flame_speed;
+                let radius = self.cfg.radius;
+                let evolved = self.loadout.evolved.contains(&EvolvedWeapon::DragonfireRing);
+                let texture = self.player_assets.flame.clone();
+                let atlas = TextureAtlas { layout: self.player_assets.flame_layout.clone(), index: 0 };
+                let orbit = OrbitingFlame { angle, radius, speed, contact_radius: 16.0, contact_damage: 5.0 };
+                let mut spawned = None;
+                self.commands.entity(player_entity).with_children(|p| {
+                    let mut flame = p.spawn((PlayerAura, orbit, Animator::new(&FLAME_SHEET, ClipId::Flicker), SpriteSheetBundle {
+                        texture,
+                        atlas,
+                        sprite: Sprite { custom_size: Some(Vec2::splat(32.0)), ..default() },
+                        transform: Transform::from_xyz(angle.cos() * radius, angle.sin() * radius, 0.0),
+                        ..default()
+                    }));
+                    if evolved { flame.insert(DragonfireRing); }
+                    spawned = Some(flame.id());
+                });
+                if let Some(entity) = spawned {
+                    self.pending_flames.1.push((entity, orbit));
+                }
+            }
+            UpgradeKind::FlameSpeed => {
+                for mut of in self.flames_q.iter_mut() {
+                    of.speed *= 1.20;
+                }
+                for (entity, of) in self.pending_flames.1.iter_mut() {
+                    of.speed *= 1.20;
+                    self.commands.entity(*entity).insert(*of);
+                }
+                // Update global buff so future flames inherit the current speed multiplier
+                **self.flame_speed *= 1.20;
+                // Also decrease aura damage tick interval by 20% (faster ticks), with a reasonable floor
+                let current = self.aura_timer.duration_secs();
+                self.aura_timer.set_duration_secs((current * 0.8).max(0.05));
+                self.loadout.passives.insert(PassiveKind::Tailwind);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn chest_rolls_in_one_system_see_each_others_flames() {
+        let mut app = App::new();
+        app.init_resource::<AuraConfig>()
+            .init_resource::<AuraTickTimer>()
+            .init_resource::<Loadout>()
+            .init_resource::<PlayerAssets>()
+            .init_resource::<FrameCount>()
+            .insert_resource(FlameSpeedBuff(1.0));
+        app.world.spawn(Player);
+        app.world.run_system_once(|mut upgrades: UpgradeContext| {
+            upgrades.apply(UpgradeKind::ExtraFlame);
+            upgrades.apply(UpgradeKind::ExtraFlame);
+            upgrades.apply(UpgradeKind::FlameSpeed);
+        });
+        let flames: Vec<OrbitingFlame> = app.world.query::<&OrbitingFlame>().iter(&app.world).copied().collect();
+        assert_eq!(flames.len(), 2);
+        assert!((flames[0].angle - flames[1].angle).abs() > 1.0, "two new flames must not share an angle");
+        assert!(flames.iter().all(|f| (f.speed - 1.8 * 1.2).abs() < 1e-4), "speed roll reaches flames spawned earlier in the chest");
+    }
+}
@@ -1,5 +1,11 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use bevy::utils::HashMap;
+use std::time::Duration;
 
+use super::audio::{PlaySfx, Sfx};
+use super::particles::{ParticleBurst, XP_SPARKLE};
 use super::player::{Player, PlayerStats};
 use super::states::GameState;
 
@@ -13,11 +19,108 @@ pub struct XpOrb {
     pub value: u32,
 }
 
+// Orbs pulled toward the player regardless of distance (magnet pickup)
+#[derive(Component)]
+pub struct Magnetized;
+
+const MAGNET_PULL_SPEED: f32 = 600.0;
+
+// One band of the level curve: from `from_level` on, each level needs `step` more XP than the last,
+// and the level-up out of `from_level` (to `from_level + 1`) costs a one-off `bonus` on top (late-game speed bumps)
+#[derive(Clone, Copy, Debug)]
+pub struct XpBand {
+    pub from_level: u32,
+    pub step: u32,
+    pub bonus: u32,
+}
+
+// XP required per level, as a table, a formula (bands sorted by `from_level`) or a table that hands over to the formula
+// where it ends; `PlayerStats::xp` stays a running total and the curve maps it to level/progress
+#[derive(Resource, Clone, Debug)]
+pub struct LevelCurve {
+    // Cost of the first level-up (1 -> 2)
+    pub base: u32,
+    pub bands: Vec<XpBand>,
+    // Explicit cost of each level-up (index 0 = level 1 -> 2), overriding the formula for the levels it covers
+    pub table: Vec<u32>,
+}
+
+impl Default for LevelCurve {
+    fn default() -> Self {
+        LevelCurve {
+            base: 100,
+            bands: vec![
+                XpBand { from_level: 1, step: 20, bonus: 0 },
+                XpBand { from_level: 20, step: 40, bonus: 600 },
+                XpBand { from_level: 40, step: 60, bonus: 2400 },
+            ],
+            table: Vec::new(),
+        }
+    }
+}
+
+impl LevelCurve {
+    // Cost of each level-up in turn, starting at level 1; the step is carried along so walking n levels is O(n).
+    // The formula keeps counting under the table, so past its end the costs pick up where the bands would be.
+    fn costs(&self) -> impl Iterator<Item = u32> + '_ {
+        let mut req = self.base;
+        (1u32..).map(move |level| {
+            if level > 1 {
+                req += self.bands.iter().rev().find(|b| b.from_level <= level).map_or(0, |b| b.step);
+            }
+            let bonus = self.bands.iter().find(|b| b.from_level == level && level > 1).map_or(0, |b| b.bonus);
+            self.table.get(level as usize - 1).copied().unwrap_or(req + bonus).max(1)
+        })
+    }
+
+    /// Level reached with `total_xp`, plus XP into that level and XP needed to finish it.
+    /// Walks the curve once, so a single large pickup correctly spans several levels.
+    pub fn progress(&self, total_xp: u32) -> (u32, u32, u32) {
+        let mut remaining = total_xp;
+        for (level, need) in (1u32..).zip(self.costs()) {
+            if remaining < need { return (level, remaining, need); }
+            remaining -= need;
+        }
+        unreachable!("the cost sequence never ends")
+    }
+
+    pub fn level_for(&self, total_xp: u32) -> u32 { self.progress(total_xp).0 }
+}
+
+// Orbs pile up on long runs; past `threshold` they get folded into gems per grid cell.
+// Orbs within `keep_radius` of the player (roughly the view) are left alone so nothing snaps together on screen.
+#[derive(Resource)]
+pub struct XpOrbMergeConfig {
+    pub threshold: usize,
+    pub cell_size: f32,
+    pub keep_radius: f32,
+}
+
+impl Default for XpOrbMergeConfig {
+    fn default() -> Self { Self { threshold: 250, cell_size: 192.0, keep_radius: 800.0 } }
+}
+
+const ORB_MERGE_CHECK_SECONDS: f32 = 1.0;
+
+// (entity, position, value) of every orb sharing a merge cell
+type OrbCell = Vec<(Entity, Vec2, u32)>;
+
+// Bigger, differently coloured gems for higher values so merged orbs read as worth more
+fn xp_orb_look(value: u32) -> (Color, f32) {
+    match value {
+        0..=5 => (Color::rgb(0.2, 1.0, 0.4), 10.0),
+        6..=50 => (Color::rgb(0.2, 0.6, 1.0), 14.0),
+        51..=250 => (Color::rgb(0.7, 0.3, 1.0), 18.0),
+        _ => (Color::rgb(1.0, 0.25, 0.3), 22.0),
+    }
+}
+
 pub fn spawn_xp_orb_at(commands: &mut Commands, pos: Vec2, value: u32) {
+    let (color, size) = xp_orb_look(value);
     commands.spawn((
         XpOrb { value },
         SpriteBundle {
-            sprite: Sprite { color: Color::rgb(0.2, 1.0, 0.4), custom_size: Some(Vec2::splat(10.0)), ..default() },
+            sprite: Sprite { color, custom_size: Some(Vec2::splat(size)), ..default() },
             transform: Transform::from_xyz(pos.x, pos.y, 0.0),
             ..default()
         },
@@ -27,26 +130,40 @@ pub fn spawn_xp_orb_at(commands: &mut Commands, pos: Vec2, value: u32) {
 impl Plugin for XpPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PendingLevelUps>()
+            .init_resource::<LevelCurve>()
+            .init_resource::<XpOrbMergeConfig>()
             .add_systems(Update, (
+                attract_magnetized_orbs,
                 pickup_xp_orbs,
                 enter_levelup_when_pending,
+                // After pickup, so orbs collected this frame are already despawned and can't be counted twice
+                merge_xp_orbs.after(pickup_xp_orbs).run_if(on_timer(Duration::from_secs_f32(ORB_MERGE_CHECK_SECONDS))),
             ).run_if(in_state(GameState::Playing)));
     }
 }
 
+// Sound and sparkle for each collected orb
+#[derive(SystemParam)]
+struct PickupFx<'w> {
+    sfx: EventWriter<'w, PlaySfx>,
+    bursts: EventWriter<'w, ParticleBurst>,
+}
+
 fn pickup_xp_orbs(
     mut commands: Commands,
     player: Query<&Transform, With<Player>>,
     mut stats: ResMut<PlayerStats>,
     mut pending: ResMut<PendingLevelUps>,
+    curve: Res<LevelCurve>,
     orbs: Query<(Entity, &Transform, &XpOrb)>,
+    mut fx: PickupFx,
 ) {
     let Ok(player_tf) = player.get_single() else { return; };
     for (entity, tf, orb) in orbs.iter() {
         if player_tf.translation.truncate().distance(tf.translation.truncate()) < 28.0 {
             stats.xp += orb.value;
-            // simple level formula: every 100 xp => level up & heal small amount
-            let expected_level = stats.xp / 100 + 1;
+            // level up (possibly several at once) & heal
+            let expected_level = curve.level_for(stats.xp);
             if expected_level > stats.level {
                 let gained = expected_level - stats.level;
                 stats.level = expected_level;
@@ -55,11 +172,58 @@ fn pickup_xp_orbs(
                 pending.0 += gained; // queue selections
             }
             commands.entity(entity).despawn();
+            fx.sfx.send(PlaySfx(Sfx::XpPickup));
+            fx.bursts.send(ParticleBurst { pos: tf.translation.truncate(), def: &XP_SPARKLE, count: 6 });
+        }
+    }
+}
+
+fn attract_magnetized_orbs(
+    time: Res<Time>,
+    player: Query<&Transform, With<Player>>,
+    mut orbs: Query<&mut Transform, (With<Magnetized>, Without<Player>)>,
+) {
+    let Ok(player_tf) = player.get_single() else { return; };
+    let target = player_tf.translation.truncate();
+    let step = MAGNET_PULL_SPEED * time.delta_seconds();
+    for mut tf in orbs.iter_mut() {
+        let pos = tf.translation.truncate();
+        let next = pos + (target - pos).clamp_length_max(step);
+        tf.translation.x = next.x;
+        tf.translation.y = next.y;
+    }
+}
+
+fn merge_xp_orbs(
+    mut commands: Commands,
+    cfg: Res<XpOrbMergeConfig>,
+    player: Query<&Transform, With<Player>>,
+    // Orbs already flying to the player are left alone
+    orbs: Query<(Entity, &Transform, &XpOrb), Without<Magnetized>>,
+) {
+    if orbs.iter().len() <= cfg.threshold { return; }
+    let player_pos = player.get_single().ok().map(|tf| tf.translation.truncate());
+    // Bucket distant orbs by grid cell, then replace every multi-orb cell with one gem carrying the summed value
+    let mut cells: HashMap<(i32, i32), OrbCell> = HashMap::default();
+    for (entity, tf, orb) in orbs.iter() {
+        let pos = tf.translation.truncate();
+        if player_pos.is_some_and(|p| p.distance(pos) < cfg.keep_radius) { continue; }
+        let key = ((pos.x / cfg.cell_size).floor() as i32, (pos.y / cfg.cell_size).floor() as i32);
+        cells.entry(key).or_default().push((entity, pos, orb.value));
+    }
+    for group in cells.values().filter(|g| g.len() > 1) {
+        let total: u32 = group.iter().map(|(_, _, v)| v).sum();
+        // Value-weighted centroid keeps the gem near where most of the XP was
+        let weight = total.max(1) as f32;
+        let center = group.iter().fold(Vec2::ZERO, |acc, (_, p, v)| acc + *p * (*v as f32)) / weight;
+        for (entity, _, _) in group.iter() {
+            commands.entity(*entity).despawn();
         }
+        spawn_xp_orb_at(&mut commands, center, total);
     }
 }
 
-fn enter_levelup_when_pending(
+pub fn enter_levelup_when_pending(
     pending: Res<PendingLevelUps>,
     mut next: ResMut<NextState<GameState>>,
 ) {
@@ -67,3 +231,114 @@ fn enter_levelup_when_pending(
         next.set(GameState::LevelUp);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total_xp(app: &mut App) -> (usize, u32) {
+        let mut q = app.world.query::<&XpOrb>();
+        let orbs: Vec<u32> = q.iter(&app.world).map(|o| o.value).collect();
+        (orbs.len(), orbs.iter().sum())
+    }
+
+    #[test]
+    fn merging_conserves_total_xp() {
+        let mut app = App::new();
+        app.insert_resource(XpOrbMergeConfig { threshold: 10, cell_size: 100.0, keep_radius: 0.0 })
+            .add_systems(Update, merge_xp_orbs);
+        for i in 0..200u32 {
+            let pos = Vec2::new((i % 20) as f32 * 37.0 - 300.0, (i / 20) as f32 * 53.0 - 250.0);
+            app.world.spawn((XpOrb { value: 1 + i % 7 }, Transform::from_xyz(pos.x, pos.y, 0.0)));
+        }
+        let (before_count, before_xp) = total_xp(&mut app);
+        app.update();
+        let (after_count, after_xp) = total_xp(&mut app);
+        assert_eq!(before_xp, after_xp);
+        assert!(after_count < before_count);
+    }
+
+    // Player at the origin with a pile of orbs under it and more scattered far away
+    fn app_with_player_and_orbs() -> App {
+        let mut app = App::new();
+        app.insert_resource(XpOrbMergeConfig { threshold: 10, cell_size: 100.0, keep_radius: 300.0 })
+            .init_resource::<PlayerStats>()
+            .init_resource::<PendingLevelUps>()
+            .init_resource::<LevelCurve>()
+            .add_event::<PlaySfx>()
+            .add_event::<ParticleBurst>()
+            .add_systems(Update, (pickup_xp_orbs, merge_xp_orbs.after(pickup_xp_orbs)));
+        app.world.spawn((Player, Transform::default()));
+        for i in 0..10 {
+            app.world.spawn((XpOrb { value: 3 }, Transform::from_xyz(i as f32, 0.0, 0.0)));
+            app.world.spawn((XpOrb { value: 3 }, Transform::from_xyz(150.0, i as f32, 0.0)));
+        }
+        for i in 0..40 {
+            app.world.spawn((XpOrb { value: 2 }, Transform::from_xyz(1000.0 + (i % 8) as f32 * 30.0, (i / 8) as f32 * 30.0, 0.0)));
+        }
+        app
+    }
+
+    #[test]
+    fn pickup_and_merge_in_one_frame_conserve_xp() {
+        let mut app = app_with_player_and_orbs();
+        let (_, before) = total_xp(&mut app);
+        app.update();
+        let (_, after) = total_xp(&mut app);
+        let collected = app.world.resource::<PlayerStats>().xp;
+        assert_eq!(collected, 30, "the ten orbs under the player are picked up");
+        assert_eq!(collected + after, before);
+    }
+
+    #[test]
+    fn orbs_near_the_player_are_not_merged() {
+        let mut app = app_with_player_and_orbs();
+        app.update();
+        let mut q = app.world.query::<(&XpOrb, &Transform)>();
+        let (near, far): (Vec<_>, Vec<_>) = q.iter(&app.world).partition(|(_, tf)| tf.translation.x < 300.0);
+        assert_eq!(near.len(), 10, "orbs in view keep their own sprites");
+        assert!(far.len() < 40, "distant orbs were folded into gems");
+    }
+
+    #[test]
+    fn level_curve_handles_multi_level_overflow() {
+        let curve = LevelCurve { base: 10, bands: vec![XpBand { from_level: 1, step: 10, bonus: 0 }], table: Vec::new() };
+        assert_eq!(curve.progress(0), (1, 0, 10));
+        assert_eq!(curve.progress(35), (3, 5, 30));
+        // one pickup spanning three level-ups: 10 + 20 + 30 = 60
+        assert_eq!(curve.progress(65), (4, 5, 40));
+
+        // Same first three costs from a table, then a flat formula takes over
+        let table = LevelCurve { base: 50, bands: Vec::new(), table: vec![10, 20, 30] };
+        assert_eq!(table.progress(0), (1, 0, 10));
+        assert_eq!(table.progress(35), (3, 5, 30));
+        assert_eq!(table.progress(65), (4, 5, 50));
+        // one pickup crossing the end of the table: 10 + 20 + 30 + 50 + 50 = 160
+        assert_eq!(table.progress(165), (6, 5, 50));
+        assert_eq!(table.level_for(165), 6);
+    }
+
+    #[test]
+    fn default_curve_grows_with_bonus_bands() {
+        let curve = LevelCurve::default();
+        // XP needed to go from `level` to `level + 1`
+        let cost = |level: usize| curve.costs().nth(level - 1).unwrap();
+        assert_eq!(cost(1), 100);
+        assert_eq!(cost(2), 120);
+        assert_eq!(cost(19), 460);
+        // The bonus is paid once, on the level-up out of the band's first level
+        assert_eq!(cost(20), 460 + 40 + 600);
+        assert_eq!(cost(21), 460 + 40 + 40);
+    }
+
+    #[test]
+    fn below_threshold_leaves_orbs_alone() {
+        let mut app = App::new();
+        app.init_resource::<XpOrbMergeConfig>().add_systems(Update, merge_xp_orbs);
+        for i in 0..5 {
+            app.world.spawn((XpOrb { value: 5 }, Transform::from_xyz(i as f32, 0.0, 0.0)));
+        }
+        app.update();
+        assert_eq!(total_xp(&mut app), (5, 25));
+    }
+}